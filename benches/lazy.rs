@@ -0,0 +1,34 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rudas::data::Series;
+use std::hint::black_box;
+
+fn bench_lazy(c: &mut Criterion) {
+    let values: Vec<f64> = (0..10_000_000).map(|i| (i % 1000) as f64 * 0.5).collect();
+    let s = Series::from(&values);
+
+    let mut group = c.benchmark_group("chain_10m");
+    group.sample_size(10);
+    group.bench_function("eager", |b| {
+        b.iter(|| {
+            black_box(&s)
+                .map(|x| x * 2.0)
+                .filter_by(|x| *x > 100.0)
+                .map(|x| x + 1.0)
+                .sum()
+        })
+    });
+    group.bench_function("lazy", |b| {
+        b.iter(|| {
+            black_box(&s)
+                .lazy()
+                .mul_scalar(2.0)
+                .filter(|x| *x > 100.0)
+                .add_scalar(1.0)
+                .sum()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_lazy);
+criterion_main!(benches);
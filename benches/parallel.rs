@@ -0,0 +1,35 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rudas::data::Series;
+use std::hint::black_box;
+
+fn bench_parallel(c: &mut Criterion) {
+    let values: Vec<f64> = (0..10_000_000).map(|i| (i % 1000) as f64 * 0.5).collect();
+    let s = Series::from(&values);
+
+    let mut group = c.benchmark_group("series_10m");
+    group.sample_size(10);
+    group.bench_function("sum", |b| b.iter(|| black_box(&s).sum()));
+    group.bench_function("par_sum", |b| b.iter(|| black_box(&s).par_sum()));
+    group.bench_function("mean", |b| b.iter(|| black_box(&s).mean()));
+    group.bench_function("par_mean", |b| b.iter(|| black_box(&s).par_mean()));
+    group.bench_function("par_map", |b| {
+        b.iter(|| black_box(&s).par_map(|x| x * 2.0 + 1.0))
+    });
+    group.bench_function("par_filter_by", |b| {
+        b.iter(|| black_box(&s).par_filter_by(|x| *x > 100.0))
+    });
+    group.finish();
+
+    let keys: Vec<usize> = (0..values.len()).map(|i| i % 100).collect();
+    let groups = s.groupby(&keys).unwrap();
+    let mut group = c.benchmark_group("groupby_10m_100_groups");
+    group.sample_size(10);
+    group.bench_function("sum", |b| b.iter(|| black_box(&groups).sum()));
+    group.bench_function("par_sum", |b| b.iter(|| black_box(&groups).par_sum()));
+    group.bench_function("mean", |b| b.iter(|| black_box(&groups).mean()));
+    group.bench_function("par_mean", |b| b.iter(|| black_box(&groups).par_mean()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel);
+criterion_main!(benches);
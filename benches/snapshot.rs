@@ -0,0 +1,37 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rudas::{
+    csv::{read_csv, CsvOptions, CsvWriteOptions},
+    data::Series,
+};
+use std::hint::black_box;
+
+fn bench_snapshot(c: &mut Criterion) {
+    let values: Vec<f64> = (0..5_000_000).map(|i| (i % 1000) as f64 * 0.25).collect();
+    let s = Series::from(&values);
+
+    let mut group = c.benchmark_group("roundtrip_5m");
+    group.sample_size(10);
+    group.bench_function("snapshot", |b| {
+        b.iter(|| {
+            let mut buf = Vec::new();
+            black_box(&s).save(&mut buf).unwrap();
+            Series::<f64>::load(&buf[..]).unwrap()
+        })
+    });
+    group.bench_function("csv", |b| {
+        b.iter(|| {
+            let mut buf = Vec::new();
+            black_box(&s)
+                .to_csv(&mut buf, &CsvWriteOptions::default())
+                .unwrap();
+            read_csv(&buf[..], &CsvOptions::default())
+                .unwrap()
+                .series::<f64>("value")
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_snapshot);
+criterion_main!(benches);
@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, BooleanArray, Float32Array, Float64Array, Int32Array, Int64Array,
+        LargeStringArray, StringArray,
+    },
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+
+use crate::{
+    error::RudasError,
+    frame::{check_label_column, labels_from_column, Column, DataFrame, LABEL_COLUMN},
+};
+
+fn downcast<'a, A: 'static>(array: &'a ArrayRef, name: &str) -> Result<&'a A, RudasError> {
+    array
+        .as_any()
+        .downcast_ref::<A>()
+        .ok_or_else(|| RudasError::TypeMismatch {
+            column: name.to_string(),
+            expected: "a supported arrow array",
+        })
+}
+
+fn column_from_arrow(array: &ArrayRef, name: &str) -> Result<Column, RudasError> {
+    Ok(match array.data_type() {
+        DataType::Int64 => Column::I64(downcast::<Int64Array>(array, name)?.iter().collect()),
+        DataType::Int32 => Column::I64(
+            downcast::<Int32Array>(array, name)?
+                .iter()
+                .map(|x| x.map(i64::from))
+                .collect(),
+        ),
+        DataType::Float64 => Column::F64(downcast::<Float64Array>(array, name)?.iter().collect()),
+        DataType::Float32 => Column::F64(
+            downcast::<Float32Array>(array, name)?
+                .iter()
+                .map(|x| x.map(f64::from))
+                .collect(),
+        ),
+        DataType::Boolean => Column::Bool(downcast::<BooleanArray>(array, name)?.iter().collect()),
+        DataType::Utf8 => Column::Str(
+            downcast::<StringArray>(array, name)?
+                .iter()
+                .map(|x| x.map(str::to_string))
+                .collect(),
+        ),
+        DataType::LargeUtf8 => Column::Str(
+            downcast::<LargeStringArray>(array, name)?
+                .iter()
+                .map(|x| x.map(str::to_string))
+                .collect(),
+        ),
+        other => {
+            return Err(RudasError::InvalidArgument(format!(
+                "unsupported arrow type {} in column {}",
+                other, name
+            )))
+        }
+    })
+}
+
+impl DataFrame {
+    /// Copies the frame into a record batch of nullable `Int64`, `Float64`,
+    /// `Boolean` and `Utf8` arrays, preceded by the row labels as a
+    /// `LABEL_COLUMN` column. Fails when a column already has that name.
+    pub fn to_arrow(&self) -> Result<RecordBatch, RudasError> {
+        check_label_column(self)?;
+        let mut fields = vec![Field::new(LABEL_COLUMN, DataType::Utf8, false)];
+        let mut arrays: Vec<ArrayRef> = vec![Arc::new(StringArray::from_iter_values(self.label()))];
+        for (name, column) in self.columns() {
+            let array: ArrayRef = match column {
+                Column::I64(v) => Arc::new(Int64Array::from(v.clone())),
+                Column::F64(v) => Arc::new(Float64Array::from(v.clone())),
+                Column::Bool(v) => Arc::new(BooleanArray::from(v.clone())),
+                Column::Str(v) => Arc::new(v.iter().map(|x| x.as_deref()).collect::<StringArray>()),
+            };
+            fields.push(Field::new(name, array.data_type().clone(), true));
+            arrays.push(array);
+        }
+        Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+    }
+
+    /// Copies a record batch into a frame. Integer, float, boolean and string
+    /// arrays are supported, and a `LABEL_COLUMN` column, when present,
+    /// becomes the row labels. That column must be a single string column
+    /// without nulls.
+    pub fn from_arrow(batch: &RecordBatch) -> Result<DataFrame, RudasError> {
+        let mut df = DataFrame::new();
+        let mut label = None;
+        for (field, array) in batch.schema().fields().iter().zip(batch.columns()) {
+            let column = column_from_arrow(array, field.name())?;
+            if field.name() != LABEL_COLUMN {
+                df.add_column(field.name(), column)?;
+            } else if label.is_some() {
+                return Err(RudasError::DuplicateColumn(LABEL_COLUMN.to_string()));
+            } else {
+                label = Some(labels_from_column(column)?);
+            }
+        }
+        match label {
+            Some(label) => df.set_label(label)?,
+            None if df.width() == 0 => {
+                df.set_label((0..batch.num_rows()).map(|i| i.to_string()).collect())?
+            }
+            None => {}
+        }
+        Ok(df)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use arrow::ipc::{reader::FileReader, writer::FileWriter};
+
+    use super::*;
+
+    fn frame_with_nulls() -> DataFrame {
+        let mut df = DataFrame::from_columns(vec![
+            (
+                "s",
+                Column::Str(vec![Some("a,b".into()), None, Some("".into())]),
+            ),
+            ("f", Column::F64(vec![Some(1.0), None, Some(f64::MAX)])),
+            ("i", Column::I64(vec![None, Some(2), Some(-3)])),
+            ("b", Column::Bool(vec![Some(true), None, Some(false)])),
+        ])
+        .unwrap();
+        df.set_label(vec!["x".into(), "y".into(), "z".into()])
+            .unwrap();
+        df
+    }
+
+    #[test]
+    fn round_trips_nulls_through_a_batch() {
+        let df = frame_with_nulls();
+        let batch = df.to_arrow().unwrap();
+        assert_eq!(batch.schema().field(0).name(), LABEL_COLUMN);
+        assert_eq!(batch.num_rows(), 3);
+        for i in 1..batch.num_columns() {
+            assert_eq!(batch.column(i).null_count(), 1);
+        }
+        assert_eq!(DataFrame::from_arrow(&batch).unwrap(), df);
+    }
+
+    #[test]
+    fn round_trips_through_ipc() {
+        let df = frame_with_nulls();
+        let batch = df.to_arrow().unwrap();
+        let mut buf = Vec::new();
+        let mut writer = FileWriter::try_new(&mut buf, &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+
+        let mut reader = FileReader::try_new(Cursor::new(buf), None).unwrap();
+        let back = reader.next().unwrap().unwrap();
+        assert!(reader.next().is_none());
+        assert_eq!(DataFrame::from_arrow(&back).unwrap(), df);
+    }
+
+    #[test]
+    fn reads_narrow_types_and_rejects_others() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Float32, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None])),
+                Arc::new(Float32Array::from(vec![0.5, 1.5])),
+            ],
+        )
+        .unwrap();
+        let df = DataFrame::from_arrow(&batch).unwrap();
+        assert_eq!(df.column("a").unwrap(), &Column::I64(vec![Some(1), None]));
+        assert_eq!(df.column("b").unwrap(), &Column::from(vec![0.5, 1.5]));
+        assert_eq!(df.label(), &["0", "1"]);
+
+        let schema = Schema::new(vec![Field::new("c", DataType::UInt8, false)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(arrow::array::UInt8Array::from(vec![1u8]))],
+        )
+        .unwrap();
+        assert!(DataFrame::from_arrow(&batch).is_err());
+    }
+
+    #[test]
+    fn reserves_the_label_column_name() {
+        let df = DataFrame::from_columns(vec![(LABEL_COLUMN, Column::from(vec![1i64]))]).unwrap();
+        assert!(matches!(df.to_arrow(), Err(RudasError::InvalidArgument(_))));
+
+        let batch = |fields: Vec<Field>, arrays: Vec<ArrayRef>| {
+            RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays).unwrap()
+        };
+        let labels = || -> ArrayRef { Arc::new(StringArray::from(vec!["a"])) };
+        let twice = batch(
+            vec![
+                Field::new(LABEL_COLUMN, DataType::Utf8, false),
+                Field::new(LABEL_COLUMN, DataType::Utf8, false),
+            ],
+            vec![labels(), labels()],
+        );
+        assert!(matches!(
+            DataFrame::from_arrow(&twice),
+            Err(RudasError::DuplicateColumn(_))
+        ));
+        let numeric = batch(
+            vec![Field::new(LABEL_COLUMN, DataType::Int64, false)],
+            vec![Arc::new(Int64Array::from(vec![1]))],
+        );
+        assert!(matches!(
+            DataFrame::from_arrow(&numeric),
+            Err(RudasError::TypeMismatch { .. })
+        ));
+        let null = batch(
+            vec![Field::new(LABEL_COLUMN, DataType::Utf8, true)],
+            vec![Arc::new(StringArray::from(vec![Some("a"), None]))],
+        );
+        assert!(matches!(
+            DataFrame::from_arrow(&null),
+            Err(RudasError::MissingValue { .. })
+        ));
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, NaiveDateTime};
+
+use crate::{data::Series, error::RudasError, num::Numeric};
+
+impl<T: Numeric> Series<T, NaiveDateTime> {
+    /// Averages the values over consecutive intervals of length `freq`,
+    /// aligned to the Unix epoch and labeled by their start. The output is in
+    /// time order and intervals without observations are omitted.
+    pub fn resample_mean(&self, freq: Duration) -> Result<Series<f64, NaiveDateTime>, RudasError> {
+        let step = match freq.num_microseconds() {
+            Some(step) if step > 0 => step,
+            _ => {
+                return Err(RudasError::InvalidArgument(
+                    "freq must be a positive number of microseconds".to_string(),
+                ))
+            }
+        };
+
+        let mut buckets: BTreeMap<i64, (f64, usize)> = BTreeMap::new();
+        for (label, value) in self.label.iter().zip(&self.data) {
+            let start = label.and_utc().timestamp_micros().div_euclid(step) * step;
+            let bucket = buckets.entry(start).or_insert((0.0, 0));
+            bucket.0 += value.to_f64();
+            bucket.1 += 1;
+        }
+
+        let mut out = Series {
+            name: self.name.clone(),
+            ..Series::from_parts(
+                Vec::with_capacity(buckets.len()),
+                Vec::with_capacity(buckets.len()),
+            )
+        };
+        for (start, (sum, count)) in buckets {
+            let label = DateTime::from_timestamp_micros(start)
+                .ok_or_else(|| RudasError::InvalidArgument("timestamp out of range".to_string()))?;
+            out.label.push(label.naive_utc());
+            out.data.push(sum / count as f64);
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn resample_mean_buckets_by_duration() {
+        let day = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let at = |h, m| day.and_hms_opt(h, m, 0).unwrap();
+        let s = Series::from_label(&[1, 3, 5, 7], &[at(0, 10), at(0, 50), at(3, 0), at(2, 59)]);
+        let hourly = s.resample_mean(Duration::hours(1)).unwrap();
+        assert_eq!(hourly.label, vec![at(0, 0), at(2, 0), at(3, 0)]);
+        assert_eq!(hourly.data, vec![2.0, 7.0, 5.0]);
+        assert!(matches!(
+            s.resample_mean(Duration::zero()),
+            Err(RudasError::InvalidArgument(_))
+        ));
+    }
+}
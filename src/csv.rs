@@ -0,0 +1,733 @@
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    fs::File,
+    io::{BufRead, BufReader, Read, Write},
+    mem,
+    path::Path,
+};
+
+use crate::{
+    data::Series,
+    error::RudasError,
+    frame::{Column, DType, DataFrame},
+    record::Field,
+};
+
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    pub delimiter: char,
+    pub has_header: bool,
+    /// Column whose text is used as the row labels instead of `0..n`.
+    pub label_column: Option<String>,
+    /// Column types to use instead of inferring them.
+    pub dtypes: Vec<(String, DType)>,
+    /// Number of rows looked at when inferring column types.
+    pub infer_rows: usize,
+    /// Skip rows with the wrong number of fields instead of failing.
+    pub skip_bad_rows: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> CsvOptions {
+        CsvOptions {
+            delimiter: ',',
+            has_header: true,
+            label_column: None,
+            dtypes: Vec::new(),
+            infer_rows: 1000,
+            skip_bad_rows: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvWriteOptions {
+    pub delimiter: char,
+    pub header: bool,
+    /// Write the row labels as the first column.
+    pub write_label: bool,
+    /// Header of the label column.
+    pub label_header: String,
+    /// Digits after the decimal point for `f64` cells. By default floats are
+    /// written so that they read back as `f64`.
+    pub float_precision: Option<usize>,
+    /// Text written for missing values.
+    pub na_rep: String,
+}
+
+impl Default for CsvWriteOptions {
+    fn default() -> CsvWriteOptions {
+        CsvWriteOptions {
+            delimiter: ',',
+            header: true,
+            write_label: true,
+            label_header: String::new(),
+            float_precision: None,
+            na_rep: String::new(),
+        }
+    }
+}
+
+type RawRecord = (usize, Vec<Option<String>>);
+
+/// Column names, with the first record when it is data rather than a header.
+type Header = (Vec<String>, Option<RawRecord>);
+
+/// Splits CSV input into records, following quoted fields across lines.
+pub(crate) struct RecordReader<R> {
+    reader: R,
+    delimiter: char,
+    line: usize,
+}
+
+impl<R: BufRead> RecordReader<R> {
+    pub(crate) fn new(reader: R, delimiter: char) -> RecordReader<R> {
+        RecordReader {
+            reader,
+            delimiter,
+            line: 0,
+        }
+    }
+
+    /// Returns the next record with the line it starts on. Blank lines are
+    /// skipped. Empty unquoted fields are `None`, while `""` is an empty
+    /// string.
+    pub(crate) fn next_record(&mut self) -> Result<Option<RawRecord>, RudasError> {
+        let mut buf = String::new();
+        loop {
+            buf.clear();
+            if self.reader.read_line(&mut buf)? == 0 {
+                return Ok(None);
+            }
+            self.line += 1;
+            if !buf.trim_end_matches(['\r', '\n']).is_empty() {
+                break;
+            }
+        }
+
+        let start = self.line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut in_quotes = false;
+        loop {
+            let mut chars = buf.chars().peekable();
+            while let Some(c) = chars.next() {
+                if in_quotes {
+                    if c != '"' {
+                        field.push(c);
+                    } else if chars.peek() == Some(&'"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        in_quotes = false;
+                    }
+                } else if c == '"' && field.is_empty() && !quoted {
+                    in_quotes = true;
+                    quoted = true;
+                } else if c == self.delimiter {
+                    fields.push(finish_field(&mut field, &mut quoted));
+                } else if c != '\r' && c != '\n' {
+                    field.push(c);
+                }
+            }
+            if !in_quotes {
+                break;
+            }
+            buf.clear();
+            if self.reader.read_line(&mut buf)? == 0 {
+                return Err(RudasError::Parse {
+                    line: start,
+                    message: "unterminated quoted field".to_string(),
+                });
+            }
+            self.line += 1;
+        }
+        fields.push(finish_field(&mut field, &mut quoted));
+        Ok(Some((start, fields)))
+    }
+}
+
+fn finish_field(field: &mut String, quoted: &mut bool) -> Option<String> {
+    let text = mem::take(field);
+    let was_quoted = mem::replace(quoted, false);
+    (was_quoted || !text.is_empty()).then_some(text)
+}
+
+/// Reads CSV data into a `DataFrame`.
+///
+/// Without an override in `options.dtypes`, each column becomes the first of
+/// `i64`, `f64`, `bool` and `str` that fits every non-empty cell among the
+/// first `options.infer_rows` rows. When a later cell does not fit, the
+/// type is inferred again from all rows, so `1` followed by `1.5` gives an
+/// `f64` column. Empty cells are missing values.
+pub fn read_csv<R: Read>(reader: R, options: &CsvOptions) -> Result<DataFrame, RudasError> {
+    let mut records = RecordReader::new(BufReader::new(reader), options.delimiter);
+    let (names, first) = match read_header(&mut records, options)? {
+        Some(header) => header,
+        None => return Ok(DataFrame::new()),
+    };
+    let mut rows: Vec<RawRecord> = first.into_iter().collect();
+    while let Some(row) = read_row(&mut records, names.len(), options)? {
+        rows.push(row);
+    }
+    build_frame(&names, &rows, options)
+}
+
+/// Reads the column names, returning the first record too when it is data.
+fn read_header<R: BufRead>(
+    records: &mut RecordReader<R>,
+    options: &CsvOptions,
+) -> Result<Option<Header>, RudasError> {
+    let first = match records.next_record()? {
+        Some(record) => record,
+        None => return Ok(None),
+    };
+    Ok(Some(if options.has_header {
+        let names = first.1.into_iter().map(Option::unwrap_or_default).collect();
+        (names, None)
+    } else {
+        let names = (0..first.1.len()).map(|i| i.to_string()).collect();
+        (names, Some(first))
+    }))
+}
+
+/// Reads the next record with `width` fields, skipping or rejecting others.
+fn read_row<R: BufRead>(
+    records: &mut RecordReader<R>,
+    width: usize,
+    options: &CsvOptions,
+) -> Result<Option<RawRecord>, RudasError> {
+    while let Some((line, fields)) = records.next_record()? {
+        if fields.len() == width {
+            return Ok(Some((line, fields)));
+        } else if !options.skip_bad_rows {
+            return Err(RudasError::Parse {
+                line,
+                message: format!("expected {} fields, found {}", width, fields.len()),
+            });
+        }
+    }
+    Ok(None)
+}
+
+pub fn read_csv_path<P: AsRef<Path>>(
+    path: P,
+    options: &CsvOptions,
+) -> Result<DataFrame, RudasError> {
+    read_csv(File::open(path)?, options)
+}
+
+/// Reads a CSV file as frames of at most `chunk_size` rows, so that files
+/// larger than memory can be processed piece by piece.
+///
+/// Column types are inferred from the first chunk, as `read_csv` does, and
+/// reused for the rest. Without a label column the rows are labeled by their
+/// position in the whole file.
+pub fn read_csv_chunks<P: AsRef<Path>>(
+    path: P,
+    chunk_size: usize,
+    options: &CsvOptions,
+) -> Result<CsvChunks<BufReader<File>>, RudasError> {
+    if chunk_size == 0 {
+        return Err(RudasError::InvalidArgument(
+            "chunk_size must be at least 1".to_string(),
+        ));
+    }
+    let mut records = RecordReader::new(BufReader::new(File::open(path)?), options.delimiter);
+    let (names, pending) = read_header(&mut records, options)?.unwrap_or_default();
+    Ok(CsvChunks {
+        records,
+        names,
+        pending,
+        options: options.clone(),
+        chunk_size,
+        offset: 0,
+        done: false,
+    })
+}
+
+/// Iterator over the frames of `read_csv_chunks`. It stops after the first
+/// error.
+pub struct CsvChunks<R> {
+    records: RecordReader<R>,
+    names: Vec<String>,
+    pending: Option<RawRecord>,
+    options: CsvOptions,
+    chunk_size: usize,
+    offset: usize,
+    done: bool,
+}
+
+impl<R: BufRead> CsvChunks<R> {
+    fn next_chunk(&mut self) -> Result<Option<DataFrame>, RudasError> {
+        let mut rows: Vec<RawRecord> = self.pending.take().into_iter().collect();
+        while rows.len() < self.chunk_size {
+            match read_row(&mut self.records, self.names.len(), &self.options)? {
+                Some(row) => rows.push(row),
+                None => break,
+            }
+        }
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let mut df = build_frame(&self.names, &rows, &self.options)?;
+        if self.offset == 0 {
+            self.options.dtypes = df
+                .columns()
+                .iter()
+                .map(|(name, column)| (name.clone(), column.dtype()))
+                .collect();
+        }
+        if self.options.label_column.is_none() {
+            df.set_label(
+                (self.offset..self.offset + rows.len())
+                    .map(|i| i.to_string())
+                    .collect(),
+            )?;
+        }
+        self.offset += rows.len();
+        Ok(Some(df))
+    }
+}
+
+impl<R: BufRead> Iterator for CsvChunks<R> {
+    type Item = Result<DataFrame, RudasError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let chunk = self.next_chunk().transpose();
+        self.done = !matches!(chunk, Some(Ok(_)));
+        chunk
+    }
+}
+
+fn build_frame(
+    names: &[String],
+    rows: &[RawRecord],
+    options: &CsvOptions,
+) -> Result<DataFrame, RudasError> {
+    let label_pos = match &options.label_column {
+        Some(name) => Some(
+            names
+                .iter()
+                .position(|n| n == name)
+                .ok_or_else(|| RudasError::ColumnNotFound(name.clone()))?,
+        ),
+        None => None,
+    };
+    for (name, _) in &options.dtypes {
+        if !names.contains(name) {
+            return Err(RudasError::ColumnNotFound(name.clone()));
+        }
+    }
+
+    let mut df = DataFrame::new();
+    for (i, name) in names.iter().enumerate() {
+        if Some(i) == label_pos {
+            continue;
+        }
+        let column = match options.dtypes.iter().find(|(n, _)| n == name) {
+            Some((_, dtype)) => parse_column(name, *dtype, rows, i)?,
+            None => {
+                let cells = rows.iter().map(|(_, r)| &r[i]);
+                let dtype = infer_dtype(cells.clone().take(options.infer_rows));
+                // A cell past the sample may not fit; inferring from every
+                // row then widens the type until all cells fit.
+                match parse_column(name, dtype, rows, i) {
+                    Ok(column) => column,
+                    Err(_) => parse_column(name, infer_dtype(cells), rows, i)?,
+                }
+            }
+        };
+        df.add_column(name, column)?;
+    }
+    if let Some(pos) = label_pos {
+        df.set_label(
+            rows.iter()
+                .map(|(_, r)| r[pos].clone().unwrap_or_default())
+                .collect(),
+        )?;
+    }
+    Ok(df)
+}
+
+pub(crate) fn parse_bool(text: &str) -> Option<bool> {
+    if text.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if text.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn infer_dtype<'a>(cells: impl Iterator<Item = &'a Option<String>> + Clone) -> DType {
+    let mut present = cells.flatten().map(|t| t.trim()).peekable();
+    if present.peek().is_none() {
+        return DType::Str;
+    }
+    if present.clone().all(|t| t.parse::<i64>().is_ok()) {
+        DType::I64
+    } else if present.clone().all(|t| t.parse::<f64>().is_ok()) {
+        DType::F64
+    } else if present.all(|t| parse_bool(t).is_some()) {
+        DType::Bool
+    } else {
+        DType::Str
+    }
+}
+
+fn parse_cells<T>(
+    name: &str,
+    dtype: DType,
+    rows: &[RawRecord],
+    i: usize,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Vec<Option<T>>, RudasError> {
+    rows.iter()
+        .map(|(line, fields)| match &fields[i] {
+            Some(text) => parse(text).map(Some).ok_or_else(|| RudasError::Parse {
+                line: *line,
+                message: format!("cannot parse '{}' as {} in column {}", text, dtype, name),
+            }),
+            None => Ok(None),
+        })
+        .collect()
+}
+
+fn parse_column(
+    name: &str,
+    dtype: DType,
+    rows: &[RawRecord],
+    i: usize,
+) -> Result<Column, RudasError> {
+    Ok(match dtype {
+        DType::I64 => Column::I64(parse_cells(name, dtype, rows, i, |t| {
+            t.trim().parse().ok()
+        })?),
+        DType::F64 => Column::F64(parse_cells(name, dtype, rows, i, |t| {
+            t.trim().parse().ok()
+        })?),
+        DType::Bool => Column::Bool(parse_cells(name, dtype, rows, i, |t| parse_bool(t.trim()))?),
+        DType::Str => Column::Str(parse_cells(name, dtype, rows, i, |t| Some(t.to_string()))?),
+    })
+}
+
+impl<T: Field + Clone> Series<T, String> {
+    /// Reads a single column of a CSV file with the default options, labeled
+    /// `0..n`.
+    pub fn from_csv_column<P: AsRef<Path>>(
+        path: P,
+        column: &str,
+    ) -> Result<Series<T, String>, RudasError> {
+        read_csv_path(path, &CsvOptions::default())?.series(column)
+    }
+}
+
+/// Quotes a field when it contains the delimiter, a quote or a line break,
+/// or when it is empty so that it is not read back as a missing value.
+fn escape_field(field: &str, delimiter: char) -> Cow<'_, str> {
+    if field.is_empty() || field.contains([delimiter, '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+fn write_record<'a, W: Write>(
+    writer: &mut W,
+    fields: impl Iterator<Item = Cow<'a, str>>,
+    delimiter: char,
+) -> Result<(), RudasError> {
+    let mut line = String::new();
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            line.push(delimiter);
+        }
+        line.push_str(&field);
+    }
+    line.push('\n');
+    writer.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+impl DataFrame {
+    /// Writes the frame as CSV, one row at a time.
+    pub fn to_csv<W: Write>(
+        &self,
+        mut writer: W,
+        options: &CsvWriteOptions,
+    ) -> Result<(), RudasError> {
+        let d = options.delimiter;
+        if options.header {
+            let label = options
+                .write_label
+                .then(|| escape_field(&options.label_header, d));
+            let names = self.columns().iter().map(|(name, _)| escape_field(name, d));
+            write_record(&mut writer, label.into_iter().chain(names), d)?;
+        }
+        for (i, label) in self.label().iter().enumerate() {
+            let label = options.write_label.then(|| escape_field(label, d));
+            let cells = self.columns().iter().map(|(_, column)| {
+                match column.format_cell(i, options.float_precision) {
+                    Some(text) => Cow::Owned(escape_field(&text, d).into_owned()),
+                    None => Cow::Borrowed(options.na_rep.as_str()),
+                }
+            });
+            write_record(&mut writer, label.into_iter().chain(cells), d)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<T: Field + Clone, U: PartialEq + Clone + Display> Series<T, U> {
+    /// Writes the series as a frame with a single `value` column, so that
+    /// the options apply as in `DataFrame::to_csv`.
+    pub fn to_csv<W: Write>(&self, writer: W, options: &CsvWriteOptions) -> Result<(), RudasError> {
+        self.to_frame("value").to_csv(writer, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_csv_handles_quoted_fields() {
+        let text = "id,name,score,ok\n1,\"a, b\",1.5,true\n2,\"multi\nline \"\"q\"\"\",,false\r\n\n3,,2,TRUE\n";
+        let df = read_csv(text.as_bytes(), &CsvOptions::default()).unwrap();
+        assert_eq!(df.column("id").unwrap(), &Column::from(vec![1i64, 2, 3]));
+        assert_eq!(
+            df.column("name").unwrap(),
+            &Column::Str(vec![
+                Some("a, b".into()),
+                Some("multi\nline \"q\"".into()),
+                None
+            ])
+        );
+        assert_eq!(
+            df.column("score").unwrap(),
+            &Column::F64(vec![Some(1.5), None, Some(2.0)])
+        );
+        assert_eq!(
+            df.column("ok").unwrap(),
+            &Column::from(vec![true, false, true])
+        );
+        assert_eq!(df.label(), &["0", "1", "2"]);
+    }
+
+    #[test]
+    fn read_csv_applies_options() {
+        let text = "id,name,score\n1,a,1.5\n2,b,\n";
+        let options = CsvOptions {
+            label_column: Some("id".into()),
+            dtypes: vec![("score".into(), DType::Str)],
+            ..Default::default()
+        };
+        let df = read_csv(text.as_bytes(), &options).unwrap();
+        assert_eq!(df.label(), &["1", "2"]);
+        assert_eq!(df.column_names(), vec!["name", "score"]);
+        assert_eq!(df.column("score").unwrap().dtype(), DType::Str);
+
+        let options = CsvOptions {
+            delimiter: ';',
+            has_header: false,
+            ..Default::default()
+        };
+        let df = read_csv("1;2\n3;4".as_bytes(), &options).unwrap();
+        assert_eq!(df.column_names(), vec!["0", "1"]);
+        assert_eq!(df.len(), 2);
+    }
+
+    #[test]
+    fn read_csv_reports_bad_rows() {
+        let bad = "a,b\n1,2\n3\n4,5\n";
+        match read_csv(bad.as_bytes(), &CsvOptions::default()) {
+            Err(RudasError::Parse { line, .. }) => assert_eq!(line, 3),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        let options = CsvOptions {
+            skip_bad_rows: true,
+            ..Default::default()
+        };
+        assert_eq!(read_csv(bad.as_bytes(), &options).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn read_csv_widens_types_past_the_sample() {
+        let mut text = String::from("x,y\n");
+        for i in 0..1000 {
+            text.push_str(&format!("{},{}\n", i, i));
+        }
+        text.push_str("1.5,word\n");
+        let df = read_csv(text.as_bytes(), &CsvOptions::default()).unwrap();
+        assert_eq!(df.column("x").unwrap().dtype(), DType::F64);
+        assert_eq!(df.column("x").unwrap().f64_at(1000), Some(1.5));
+        assert_eq!(df.column("y").unwrap().dtype(), DType::Str);
+
+        let options = CsvOptions {
+            dtypes: vec![("x".into(), DType::I64)],
+            ..Default::default()
+        };
+        assert!(matches!(
+            read_csv(text.as_bytes(), &options),
+            Err(RudasError::Parse { line: 1002, .. })
+        ));
+    }
+
+    #[test]
+    fn from_csv_column_reads_one_column() {
+        let path = std::env::temp_dir().join("rudas_from_csv_column.csv");
+        std::fs::write(&path, "x,y\n1,2\n,3\n").unwrap();
+        let s: Series<Option<i64>, String> = Series::from_csv_column(&path, "x").unwrap();
+        assert_eq!(s.data, vec![Some(1), None]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn series_to_csv_round_trips_floats() {
+        let s = Series::from(&[1.0, 2.0, 3.5]);
+        let options = CsvWriteOptions {
+            label_header: "label".to_string(),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        s.to_csv(&mut out, &options).unwrap();
+        assert_eq!(
+            String::from_utf8(out.clone()).unwrap(),
+            "label,value\n0,1.0\n1,2.0\n2,3.5\n"
+        );
+        let options = CsvOptions {
+            label_column: Some("label".to_string()),
+            ..Default::default()
+        };
+        let back: Series<f64, String> = read_csv(&out[..], &options)
+            .unwrap()
+            .series("value")
+            .unwrap();
+        assert_eq!(back.data, s.data);
+        assert_eq!(back.label, vec!["0", "1", "2"]);
+    }
+
+    #[test]
+    fn series_to_csv_escapes_fields() {
+        let s = Series::from_label(
+            &["a,b".to_string(), "q\"x\"".to_string(), String::new()],
+            &[1, 2, 3],
+        );
+        let mut out = Vec::new();
+        s.to_csv(&mut out, &CsvWriteOptions::default()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\"\",value\n1,\"a,b\"\n2,\"q\"\"x\"\"\"\n3,\"\"\n"
+        );
+    }
+
+    #[test]
+    fn series_to_csv_applies_options_to_missing_values() {
+        let s = Series::from(&[Some(1.25), None]);
+        let options = CsvWriteOptions {
+            write_label: false,
+            float_precision: Some(1),
+            na_rep: "NA".to_string(),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        s.to_csv(&mut out, &options).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "value\n1.2\nNA\n");
+    }
+
+    #[test]
+    fn frame_to_csv_round_trips() {
+        let mut df = DataFrame::from_columns(vec![
+            (
+                "s",
+                Column::Str(vec![
+                    Some("a,b".into()),
+                    Some("q\"x\"\nz".into()),
+                    Some("".into()),
+                    None,
+                ]),
+            ),
+            (
+                "f",
+                Column::F64(vec![Some(1.0), None, Some(2.5), Some(1e16)]),
+            ),
+            ("i", Column::from(vec![1i64, 2, 3, 4])),
+            (
+                "b",
+                Column::Bool(vec![Some(true), None, Some(false), Some(true)]),
+            ),
+        ])
+        .unwrap();
+        df.set_label(vec!["x".into(), "y,z".into(), "w".into(), "v".into()])
+            .unwrap();
+        let mut out = Vec::new();
+        df.to_csv(&mut out, &CsvWriteOptions::default()).unwrap();
+        let options = CsvOptions {
+            label_column: Some("".into()),
+            ..Default::default()
+        };
+        assert_eq!(read_csv(&out[..], &options).unwrap(), df);
+
+        let mut out = Vec::new();
+        let options = CsvWriteOptions {
+            write_label: false,
+            float_precision: Some(2),
+            na_rep: "NA".into(),
+            ..Default::default()
+        };
+        df.to_csv(&mut out, &options).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .starts_with("s,f,i,b\n\"a,b\",1.00,1,true\n"));
+    }
+
+    #[test]
+    fn read_csv_chunks_matches_a_whole_read() {
+        let path = std::env::temp_dir().join(format!("rudas_chunks_{}.csv", std::process::id()));
+        let mut text = String::from("k,v\n");
+        for i in 0..25 {
+            let v = if i == 20 {
+                "1.5".to_string()
+            } else {
+                i.to_string()
+            };
+            text.push_str(&format!("{},{}\n", ["a", "b", "c"][i % 3], v));
+        }
+        std::fs::write(&path, text).unwrap();
+
+        let inferred: Vec<_> = read_csv_chunks(&path, 10, &CsvOptions::default())
+            .unwrap()
+            .collect();
+        assert_eq!(inferred.len(), 3);
+        assert!(inferred[2].is_err());
+
+        let options = CsvOptions {
+            dtypes: vec![("v".into(), DType::F64)],
+            ..Default::default()
+        };
+        let whole = read_csv_path(&path, &options).unwrap();
+        let chunks: Vec<DataFrame> = read_csv_chunks(&path, 10, &options)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let zero_chunk = read_csv_chunks(&path, 0, &options).is_err();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            chunks.iter().map(DataFrame::len).collect::<Vec<_>>(),
+            vec![10, 10, 5]
+        );
+        let labels: Vec<String> = chunks.iter().flat_map(|df| df.label().to_vec()).collect();
+        assert_eq!(labels, whole.label());
+        assert_eq!(chunks[2].column("v").unwrap().f64_at(0), Some(1.5));
+        assert!(zero_chunk);
+    }
+}
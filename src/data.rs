@@ -1,20 +1,2724 @@
-use std::{any::type_name, fmt::Display};
+use std::{
+    any::type_name,
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt::{self, Debug, Display},
+    hash::Hash,
+    iter,
+    ops::{Not, Range},
+    str::FromStr,
+};
 
-#[derive(Debug, Clone)]
-pub struct Series<T: Clone>(Vec<T>);
+use crate::{
+    error::RudasError,
+    frame::pearson,
+    interval::{Closed, Interval},
+    num::Numeric,
+    stream::ValueCounter,
+};
+
+/// Values with one label each.
+///
+/// `clone` copies both vectors element by element with `T::clone` and
+/// `U::clone`, so the copy owns its data: values holding heap data such as
+/// `Vec` or `String` are fully independent of the original. Only types whose
+/// own `Clone` shares state, like `Rc`, stay shared.
+///
+/// `Debug` shows the length and at most `DEBUG_PREVIEW` values and labels.
+///
+/// `name` and `index_name` optionally describe the values and the labels.
+/// Operations that derive a series from one source keep its names.
+/// Struct literals outside this crate should end with `..Default::default()`
+/// so that they keep compiling as fields are added.
+#[derive(Clone)]
+pub struct Series<T: Clone, U: PartialEq + Clone = usize> {
+    pub data: Vec<T>,
+    pub label: Vec<U>,
+    pub name: Option<String>,
+    pub index_name: Option<String>,
+}
+
+/// Number of values and labels shown by the `Debug` output of a `Series`.
+pub const DEBUG_PREVIEW: usize = 10;
 
 impl<T: Clone> Series<T> {
     pub fn from(v: &[T]) -> Series<T> {
-        Series(v.to_vec())
+        Series::from_parts(v.to_vec(), (0..v.len()).collect())
+    }
+
+    /// Expands `(value, length)` runs into a series labeled `0..n`. The
+    /// triples of `run_lengths` convert with `map(|(v, n, _)| (v, n))`, and
+    /// their start labels are not kept.
+    pub fn from_runs(runs: &[(T, usize)]) -> Series<T> {
+        let data: Vec<T> = runs
+            .iter()
+            .flat_map(|(value, n)| iter::repeat_n(value.clone(), *n))
+            .collect();
+        let label = (0..data.len()).collect();
+        Series::from_parts(data, label)
+    }
+}
+
+impl<T: Clone, U: PartialEq + Clone> Series<T, U> {
+    pub fn from_label(data: &[T], label: &[U]) -> Series<T, U> {
+        assert_eq!(
+            data.len(),
+            label.len(),
+            "data and label must have the same length"
+        );
+        Series::from_parts(data.to_vec(), label.to_vec())
+    }
+
+    pub fn with_name(mut self, name: &str) -> Series<T, U> {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn with_index_name(mut self, name: &str) -> Series<T, U> {
+        self.index_name = Some(name.to_string());
+        self
+    }
+
+    /// An unnamed series. The lengths are not checked.
+    pub(crate) fn from_parts(data: Vec<T>, label: Vec<U>) -> Series<T, U> {
+        Series {
+            data,
+            label,
+            name: None,
+            index_name: None,
+        }
+    }
+
+    /// A series with new values and labels that keeps the names, for
+    /// subsets and reorderings of this one.
+    pub(crate) fn with_parts<R: Clone, V: PartialEq + Clone>(
+        &self,
+        data: Vec<R>,
+        label: Vec<V>,
+    ) -> Series<R, V> {
+        Series {
+            data,
+            label,
+            name: self.name.clone(),
+            index_name: self.index_name.clone(),
+        }
+    }
+
+    /// A series with `data` in place of the values, keeping the labels and
+    /// names.
+    pub(crate) fn with_data<R: Clone>(&self, data: Vec<R>) -> Series<R, U> {
+        Series {
+            data,
+            label: self.label.clone(),
+            name: self.name.clone(),
+            index_name: self.index_name.clone(),
+        }
+    }
+
+    fn with_names_of<R: Clone, V: PartialEq + Clone>(
+        mut self,
+        source: &Series<R, V>,
+    ) -> Series<T, U> {
+        self.name = source.name.clone();
+        self.index_name = source.index_name.clone();
+        self
+    }
+
+    pub fn into_parts(self) -> (Vec<T>, Vec<U>) {
+        (self.data, self.label)
+    }
+
+    pub fn as_parts(&self) -> (&[T], &[U]) {
+        (&self.data, &self.label)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn head(&self, n: usize) -> Series<T, U> {
+        let n = n.min(self.len());
+        Series::from_label(&self.data[..n], &self.label[..n]).with_names_of(self)
+    }
+
+    pub fn tail(&self, n: usize) -> Series<T, U> {
+        let start = self.len() - n.min(self.len());
+        Series::from_label(&self.data[start..], &self.label[start..]).with_names_of(self)
+    }
+
+    /// Position of the element labeled `label`, found by a linear scan. Fails
+    /// when no element or more than one element has that label. Use
+    /// `sorted_index` for binary search on sorted labels.
+    pub fn get_loc(&self, label: &U) -> Result<usize, RudasError>
+    where
+        U: Display,
+    {
+        let mut positions = self.label.iter().enumerate().filter(|(_, l)| *l == label);
+        match (positions.next(), positions.next()) {
+            (Some((i, _)), None) => Ok(i),
+            (None, _) => Err(RudasError::LabelNotFound(label.to_string())),
+            (Some(_), Some(_)) => Err(RudasError::DuplicateEntry(label.to_string())),
+        }
+    }
+
+    /// The first value labeled `label`, found by a linear scan. Use
+    /// `build_index` for many lookups, or `sorted_index` when the labels are
+    /// sorted.
+    ///
+    /// No index is cached on the series, so `loc` never gets faster by
+    /// itself: the fields are public and a cache could not see them change.
+    /// `build_index` instead returns a `LabelIndex` that borrows the series,
+    /// which rules out mutation, and so invalidation, while it exists.
+    pub fn loc(&self, label: &U) -> Option<&T> {
+        let i = self.label.iter().position(|l| l == label)?;
+        Some(&self.data[i])
+    }
+
+    /// Hashes the labels once so that lookups through the returned index take
+    /// O(1). The index borrows the series, so the series cannot be changed
+    /// while the index exists and the index can never be stale.
+    pub fn build_index(&self) -> LabelIndex<'_, T, U>
+    where
+        U: Hash + Eq,
+    {
+        let mut positions = HashMap::with_capacity(self.len());
+        for (i, l) in self.label.iter().enumerate() {
+            positions.entry(l).or_insert(i);
+        }
+        LabelIndex {
+            series: self,
+            positions,
+        }
+    }
+
+    /// Position of every target label, or `None` when it is absent. With
+    /// duplicate labels the first position is returned.
+    pub fn get_indexer(&self, targets: &[U]) -> Vec<Option<usize>>
+    where
+        U: Hash + Eq,
+    {
+        let mut index = HashMap::with_capacity(self.len());
+        for (i, l) in self.label.iter().enumerate() {
+            index.entry(l).or_insert(i);
+        }
+        targets.iter().map(|t| index.get(t).copied()).collect()
+    }
+
+    /// Gathers the elements at `positions`, in that order and allowing
+    /// repeats.
+    pub fn take(&self, positions: &[usize]) -> Result<Series<T, U>, RudasError> {
+        if let Some(&index) = positions.iter().find(|&&i| i >= self.len()) {
+            return Err(RudasError::OutOfBounds {
+                index,
+                len: self.len(),
+            });
+        }
+        Ok(self.with_parts(
+            positions.iter().map(|&i| self.data[i].clone()).collect(),
+            positions.iter().map(|&i| self.label[i].clone()).collect(),
+        ))
+    }
+
+    /// Keeps the elements where `mask` is true. The mask must have the same
+    /// length as the series.
+    pub fn mask_select(&self, mask: &Series<bool, U>) -> Result<Series<T, U>, RudasError> {
+        self.check_zip(mask, false)?;
+        let (data, label) = self
+            .data
+            .iter()
+            .zip(&self.label)
+            .zip(&mask.data)
+            .filter(|(_, &keep)| keep)
+            .map(|((v, l), _)| (v.clone(), l.clone()))
+            .unzip();
+        Ok(self.with_parts(data, label))
+    }
+
+    /// Removes every element whose label is in `labels`. Labels that are not
+    /// present are ignored.
+    pub fn drop(&self, labels: &[U]) -> Series<T, U> {
+        let (data, label) = self
+            .data
+            .iter()
+            .zip(&self.label)
+            .filter(|(_, l)| !labels.contains(l))
+            .map(|(v, l)| (v.clone(), l.clone()))
+            .unzip();
+        self.with_parts(data, label)
+    }
+
+    /// Like `drop`, but fails when one of `labels` is not present.
+    pub fn try_drop(&self, labels: &[U]) -> Result<Series<T, U>, RudasError>
+    where
+        U: Display,
+    {
+        if let Some(missing) = labels.iter().find(|l| !self.label.contains(l)) {
+            return Err(RudasError::LabelNotFound(missing.to_string()));
+        }
+        Ok(self.drop(labels))
+    }
+
+    pub fn first(&self) -> Option<(&U, &T)> {
+        self.nth(0)
+    }
+
+    pub fn last(&self) -> Option<(&U, &T)> {
+        self.nth_from_end(0)
+    }
+
+    /// The label and value at position `n`.
+    pub fn nth(&self, n: usize) -> Option<(&U, &T)> {
+        Some((self.label.get(n)?, self.data.get(n)?))
+    }
+
+    /// The label and value `n` positions before the last one.
+    pub fn nth_from_end(&self, n: usize) -> Option<(&U, &T)> {
+        self.nth(self.len().checked_sub(n + 1)?)
+    }
+
+    /// Borrows the elements at `range` without copying them.
+    pub fn view(&self, range: Range<usize>) -> Result<SeriesView<'_, T, U>, RudasError> {
+        if range.end > self.len() {
+            return Err(RudasError::OutOfBounds {
+                index: range.end,
+                len: self.len(),
+            });
+        }
+        if range.start > range.end {
+            return Err(RudasError::InvalidArgument(
+                "range start must not exceed its end".to_string(),
+            ));
+        }
+        Ok(SeriesView {
+            data: &self.data[range.clone()],
+            label: &self.label[range],
+        })
+    }
+
+    /// Truncates the series to `n` elements or pads it with `fill`, labeling
+    /// the padded position `i` with `fill_label(i)`.
+    pub fn resize(&self, n: usize, fill: T, fill_label: impl Fn(usize) -> U) -> Series<T, U> {
+        let mut out = self.head(n);
+        out.data.resize(n, fill);
+        out.label.extend((self.len()..n).map(fill_label));
+        out
+    }
+
+    /// Moves the values by `periods` positions, forward when positive and
+    /// backward when negative, keeping the labels in place. Vacated positions
+    /// are `None`.
+    pub fn shift(&self, periods: i64) -> Series<Option<T>, U> {
+        self.with_data(self.data.iter().cloned().map(Some).collect())
+            .shift_fill(periods, None)
+    }
+
+    /// Like `shift`, but vacated positions are set to `fill`.
+    pub fn shift_fill(&self, periods: i64, fill: T) -> Series<T, U> {
+        let n = self.len();
+        let k = (periods.unsigned_abs() as usize).min(n);
+        let data = if periods >= 0 {
+            iter::repeat_n(fill, k)
+                .chain(self.data[..n - k].iter().cloned())
+                .collect()
+        } else {
+            self.data[k..]
+                .iter()
+                .cloned()
+                .chain(iter::repeat_n(fill, k))
+                .collect()
+        };
+        self.with_data(data)
+    }
+
+    /// Overlapping slices of `size` consecutive values, like
+    /// `slice::windows`. Empty when `size` exceeds the length. Panics when
+    /// `size` is 0.
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = &[T]> {
+        self.data.windows(size)
+    }
+
+    /// Non-overlapping slices of `size` values, like `slice::chunks`. The
+    /// last chunk is shorter when `size` does not divide the length. Panics
+    /// when `size` is 0.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(size)
+    }
+
+    /// Applies `f` to every trailing window of `window` elements. The first
+    /// `window - 1` positions are `None`.
+    pub fn rolling<F: Fn(&[T]) -> f64>(
+        &self,
+        window: usize,
+        f: F,
+    ) -> Result<Series<Option<f64>, U>, RudasError> {
+        self.rolling_map(window, f)
+    }
+
+    /// Like `rolling` for any result type. Every window is a contiguous
+    /// slice of the values, in their order.
+    pub fn rolling_map<R: Clone, F: Fn(&[T]) -> R>(
+        &self,
+        window: usize,
+        f: F,
+    ) -> Result<Series<Option<R>, U>, RudasError> {
+        if window == 0 {
+            return Err(RudasError::InvalidArgument(
+                "window must be at least 1".to_string(),
+            ));
+        }
+        let mut data = vec![None; self.len().min(window - 1)];
+        data.extend(self.windows(window).map(|w| Some(f(w))));
+        Ok(self.with_data(data))
+    }
+
+    pub fn count_value(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.data.iter().filter(|x| *x == value).count()
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.data.contains(value)
+    }
+
+    /// Whether both series hold the same values in the same order,
+    /// whatever their labels.
+    pub fn values_eq(&self, other: &Series<T, U>) -> bool
+    where
+        T: PartialEq,
+    {
+        self.data == other.data
+    }
+
+    /// Changes every value in place with `f`, leaving the labels as they are.
+    pub fn apply_inplace<F: Fn(&mut T)>(&mut self, f: F) {
+        self.data.iter_mut().for_each(f);
+    }
+
+    pub fn map<R: Clone, F: Fn(&T) -> R>(&self, f: F) -> Series<R, U> {
+        self.with_data(self.data.iter().map(f).collect())
+    }
+
+    /// Like `map`, but `f` also receives the label of every value.
+    pub fn map_with_label<R: Clone, F: Fn(&U, &T) -> R>(&self, f: F) -> Series<R, U> {
+        self.with_data(
+            self.label
+                .iter()
+                .zip(&self.data)
+                .map(|(l, v)| f(l, v))
+                .collect(),
+        )
+    }
+
+    /// Replaces every label with `f` of it, possibly changing the label type.
+    pub fn relabel_with<L: PartialEq + Clone, F: Fn(&U) -> L>(&self, f: F) -> Series<T, L> {
+        Series::from_parts(self.data.clone(), self.label.iter().map(f).collect())
+            .with_names_of(self)
+    }
+
+    /// Keeps the elements for which `f` returns true.
+    pub fn filter_by<F: Fn(&T) -> bool>(&self, f: F) -> Series<T, U> {
+        let (data, label) = self
+            .data
+            .iter()
+            .zip(&self.label)
+            .filter(|(v, _)| f(v))
+            .map(|(v, l)| (v.clone(), l.clone()))
+            .unzip();
+        self.with_parts(data, label)
+    }
+
+    /// Applies `f` to the values of `self` and `other` pairwise by position,
+    /// keeping the labels of `self`. The series must have the same length
+    /// and, with `check_labels`, the same labels.
+    pub fn zip_apply<T2: Clone, R: Clone>(
+        &self,
+        other: &Series<T2, U>,
+        check_labels: bool,
+        f: impl Fn(&T, &T2) -> R,
+    ) -> Result<Series<R, U>, RudasError> {
+        self.check_zip(other, check_labels)?;
+        Ok(self.with_data(
+            self.data
+                .iter()
+                .zip(&other.data)
+                .map(|(a, b)| f(a, b))
+                .collect(),
+        ))
+    }
+
+    /// Like `zip_apply` with two other series, such as for `a * b + c`.
+    pub fn zip_apply3<T2: Clone, T3: Clone, R: Clone>(
+        &self,
+        b: &Series<T2, U>,
+        c: &Series<T3, U>,
+        check_labels: bool,
+        f: impl Fn(&T, &T2, &T3) -> R,
+    ) -> Result<Series<R, U>, RudasError> {
+        self.check_zip(b, check_labels)?;
+        self.check_zip(c, check_labels)?;
+        Ok(self.with_data(
+            self.data
+                .iter()
+                .zip(&b.data)
+                .zip(&c.data)
+                .map(|((x, y), z)| f(x, y, z))
+                .collect(),
+        ))
+    }
+
+    fn check_zip<V: Clone>(
+        &self,
+        other: &Series<V, U>,
+        check_labels: bool,
+    ) -> Result<(), RudasError> {
+        if other.len() != self.len() {
+            return Err(RudasError::LengthMismatch {
+                expected: self.len(),
+                found: other.len(),
+            });
+        }
+        if check_labels && other.label != self.label {
+            return Err(RudasError::InvalidArgument(
+                "series must have the same labels".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reduces the values into a single accumulator, in order.
+    pub fn fold<A, F: Fn(A, &T) -> A>(&self, init: A, f: F) -> A {
+        self.data.iter().fold(init, f)
+    }
+
+    /// Number of distinct values seen up to and including every position.
+    pub fn cum_nunique(&self) -> Series<usize, U>
+    where
+        T: Eq + Hash,
+    {
+        let mut seen = HashSet::new();
+        self.with_data(
+            self.data
+                .iter()
+                .map(|x| {
+                    seen.insert(x);
+                    seen.len()
+                })
+                .collect(),
+        )
     }
 
+    /// Shannon entropy in bits of the distribution of values. An empty
+    /// series has no uncertainty and gives 0.
+    pub fn entropy(&self) -> f64
+    where
+        T: Eq + Hash,
+    {
+        let mut counter = ValueCounter::new();
+        counter.push_chunk(self);
+        let n = self.len() as f64;
+        counter.finish().data.iter().fold(0.0, |acc, &count| {
+            let p = count as f64 / n;
+            acc - p * p.log2()
+        })
+    }
+
+    /// Ranks of the values from 1 for the smallest. Tied values share the
+    /// average of the ranks they span. Values that do not compare with
+    /// themselves, such as `NAN`, are left out of the ranking and get a
+    /// `NAN` rank.
+    pub fn rank(&self) -> Series<f64, U>
+    where
+        T: PartialOrd,
+    {
+        let mut order: Vec<usize> = (0..self.len())
+            .filter(|&i| !is_unordered(&self.data[i]))
+            .collect();
+        order.sort_by(|&a, &b| {
+            self.data[a]
+                .partial_cmp(&self.data[b])
+                .unwrap_or(Ordering::Equal)
+        });
+        let mut ranks = vec![f64::NAN; self.len()];
+        let mut start = 0;
+        while start < order.len() {
+            let mut end = start + 1;
+            while end < order.len() && self.data[order[end]] == self.data[order[start]] {
+                end += 1;
+            }
+            let rank = (start + end + 1) as f64 / 2.0;
+            for &i in &order[start..end] {
+                ranks[i] = rank;
+            }
+            start = end;
+        }
+        self.with_data(ranks)
+    }
+
+    /// `rank` divided by the number of ranked values, in `(0, 1]`. Values
+    /// without a rank stay `NAN`.
+    pub fn pct_rank(&self) -> Series<f64, U>
+    where
+        T: PartialOrd,
+    {
+        let mut ranks = self.rank();
+        let n = ranks.data.iter().filter(|r| !r.is_nan()).count() as f64;
+        ranks.data.iter_mut().for_each(|r| *r /= n);
+        ranks
+    }
+
+    /// Marks the values in `[low, high]`, or in `(low, high)` when not
+    /// `inclusive`.
+    pub fn between(&self, low: T, high: T, inclusive: bool) -> Series<bool, U>
+    where
+        T: PartialOrd,
+    {
+        self.map(|x| {
+            if inclusive {
+                *x >= low && *x <= high
+            } else {
+                *x > low && *x < high
+            }
+        })
+    }
+
+    /// Running maximum. Values that do not compare with themselves, such as
+    /// `NAN`, are skipped and their positions repeat the running maximum, so
+    /// only leading `NAN`s stay `NAN`.
+    pub fn cummax(&self) -> Series<T, U>
+    where
+        T: PartialOrd,
+    {
+        self.running(|x, acc| x > acc)
+    }
+
+    /// Running minimum, skipping `NAN` like `cummax`.
+    pub fn cummin(&self) -> Series<T, U>
+    where
+        T: PartialOrd,
+    {
+        self.running(|x, acc| x < acc)
+    }
+
+    fn running(&self, replaces: fn(&T, &T) -> bool) -> Series<T, U>
+    where
+        T: PartialOrd,
+    {
+        let mut acc: Option<T> = None;
+        let data = self
+            .data
+            .iter()
+            .map(|x| {
+                let current = acc.get_or_insert_with(|| x.clone());
+                if replaces(x, current) || (is_unordered(current) && !is_unordered(x)) {
+                    *current = x.clone();
+                }
+                current.clone()
+            })
+            .collect();
+        self.with_data(data)
+    }
+
+    /// Marks the positions whose value differs from the previous one. The
+    /// first element is always marked.
+    pub fn changed(&self) -> Series<bool, U>
+    where
+        T: PartialEq,
+    {
+        self.with_data(
+            iter::once(!self.is_empty())
+                .take(self.len())
+                .chain(self.data.windows(2).map(|w| w[0] != w[1]))
+                .collect(),
+        )
+    }
+
+    /// Run-length encodes the values as `(value, length, first label)` for
+    /// every run of equal consecutive values, in order.
+    pub fn run_lengths(&self) -> Vec<(T, usize, U)>
+    where
+        T: PartialEq,
+    {
+        let mut runs: Vec<(T, usize, U)> = Vec::new();
+        for (value, label) in self.data.iter().zip(&self.label) {
+            match runs.last_mut() {
+                Some((last, n, _)) if last == value => *n += 1,
+                _ => runs.push((value.clone(), 1, label.clone())),
+            }
+        }
+        runs
+    }
+
+    /// Whether every value is greater than or equal to the previous one.
+    pub fn is_monotonic_increasing(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.data.windows(2).all(|w| w[0] <= w[1])
+    }
+
+    /// Whether every value is less than or equal to the previous one.
+    pub fn is_monotonic_decreasing(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.data.windows(2).all(|w| w[0] >= w[1])
+    }
+
+    /// Whether the labels are non-decreasing.
+    pub fn is_index_monotonic(&self) -> bool
+    where
+        U: PartialOrd,
+    {
+        self.label.windows(2).all(|w| w[0] <= w[1])
+    }
+
+    /// Checks once that the labels are sorted in increasing order, so that
+    /// lookups and label ranges through the returned index are binary
+    /// searches. The index borrows the series and cannot become stale.
+    pub fn sorted_index(&self) -> Result<SortedIndex<'_, T, U>, RudasError>
+    where
+        U: PartialOrd,
+    {
+        if !self.is_index_monotonic() {
+            return Err(RudasError::InvalidArgument(
+                "labels must be sorted for a sorted index".to_string(),
+            ));
+        }
+        Ok(SortedIndex { series: self })
+    }
+
+    /// Elements whose labels lie in `[start, end]`, found by binary search in
+    /// O(log n) plus the size of the range.
+    ///
+    /// The labels must be sorted in increasing order. Verifying that takes
+    /// O(n), so it is only done in debug builds; with unsorted labels the
+    /// result is unspecified. Use `sorted_index` to check the order once and
+    /// get an error instead. Since the fields are public, a sorted flag kept
+    /// on the series could go stale, while the index holds a borrow and
+    /// cannot.
+    pub fn slice_by_label_range(&self, start: &U, end: &U) -> Series<T, U>
+    where
+        U: PartialOrd,
+    {
+        debug_assert!(self.is_index_monotonic(), "labels must be sorted");
+        SortedIndex { series: self }.slice_by_label_range(start, end)
+    }
+
+    /// Pairs every value with the value of `other` at the last label not
+    /// after its own, or `None` when every label of `other` is later. Both
+    /// series must be sorted by label; a single merge pass is made.
+    pub fn merge_asof(&self, other: &Series<T, U>) -> Result<Series<(T, Option<T>), U>, RudasError>
+    where
+        U: Ord,
+    {
+        if !self.is_index_monotonic() || !other.is_index_monotonic() {
+            return Err(RudasError::InvalidArgument(
+                "labels must be sorted for an as-of merge".to_string(),
+            ));
+        }
+        let mut j = 0;
+        let data = self
+            .data
+            .iter()
+            .zip(&self.label)
+            .map(|(x, l)| {
+                while j < other.len() && other.label[j] <= *l {
+                    j += 1;
+                }
+                (x.clone(), j.checked_sub(1).map(|k| other.data[k].clone()))
+            })
+            .collect();
+        Ok(self.with_data(data))
+    }
+
+    /// Name of the value type, as given by `type_name`. The text is for
+    /// display only and may change between compiler versions.
+    pub fn dtype(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    /// Name of the label type, as given by `type_name`. Like `dtype`, it is
+    /// for display only.
+    pub fn label_dtype(&self) -> &'static str {
+        type_name::<U>()
+    }
+
+    /// Prints every label and value on its own line, then the value type.
+    /// The index and value names come first when either is set.
+    pub fn print(&self)
+    where
+        T: Display,
+        U: Display,
+    {
+        if self.name.is_some() || self.index_name.is_some() {
+            println!(
+                "{}\t{}",
+                self.index_name.as_deref().unwrap_or(""),
+                self.name.as_deref().unwrap_or("")
+            );
+        }
+        for (l, v) in self.label.iter().zip(self.data.iter()) {
+            println!("{}\t{:}", l, v);
+        }
+        println!("type : {}", self.dtype());
+    }
+
+    /// Same as `print`.
     pub fn debug(&self)
     where
         T: Display,
+        U: Display,
+    {
+        self.print();
+    }
+}
+
+impl<U: PartialEq + Clone> Series<String, U> {
+    /// Parses every string into `R`, keeping the result of each parse.
+    pub fn parse_column<R: FromStr + Clone>(&self) -> Series<Result<R, R::Err>, U>
+    where
+        R::Err: Clone,
+    {
+        self.map(|s| s.parse())
+    }
+
+    /// Parses every string into `R`, with `None` where parsing fails.
+    pub fn parse_column_lossy<R: FromStr + Clone>(&self) -> Series<Option<R>, U> {
+        self.map(|s| s.parse().ok())
+    }
+}
+
+impl<U: PartialEq + Clone> Series<f64, U> {
+    /// The values as `i64`, or `None` unless every value is whole and in the
+    /// range of `i64`.
+    pub fn to_int_lossless(&self) -> Option<Series<i64, U>> {
+        let data = self
+            .data
+            .iter()
+            .map(|&x| {
+                (x.fract() == 0.0 && x >= i64::MIN as f64 && x < i64::MAX as f64)
+                    .then_some(x as i64)
+            })
+            .collect::<Option<Vec<i64>>>()?;
+        Some(self.with_data(data))
+    }
+}
+
+impl<T: Clone, U: PartialEq + Clone> Series<Vec<T>, U> {
+    /// Flattens the lists into one element per item, repeating the label of
+    /// the list for each. Empty lists produce no elements.
+    pub fn explode(&self) -> Series<T, U> {
+        let mut out = self.with_parts(Vec::new(), Vec::new());
+        for (values, label) in self.data.iter().zip(&self.label) {
+            out.data.extend(values.iter().cloned());
+            out.label
+                .extend(iter::repeat_n(label.clone(), values.len()));
+        }
+        out
+    }
+}
+
+impl<T: Numeric, U: PartialEq + Clone> Series<T, U> {
+    pub fn sum(&self) -> T {
+        self.data.iter().fold(T::zero(), |acc, &x| acc + x)
+    }
+
+    /// Returns `T::one()` for an empty series.
+    pub fn prod(&self) -> T {
+        self.data.iter().fold(T::one(), |acc, &x| acc * x)
+    }
+
+    /// `(sum, prod, mean)` computed in a single pass.
+    pub fn agg_summary(&self) -> (T, T, f64) {
+        let (sum, prod, total) = self
+            .data
+            .iter()
+            .fold((T::zero(), T::one(), 0.0), |(sum, prod, total), &x| {
+                (sum + x, prod * x, total + x.to_f64())
+            });
+        let mean = if self.is_empty() {
+            f64::NAN
+        } else {
+            total / self.len() as f64
+        };
+        (sum, prod, mean)
+    }
+
+    /// Element-wise sum with `other`, `None` where it overflows `T`. Both
+    /// series must have the same labels.
+    pub fn checked_add(&self, other: &Series<T, U>) -> Result<Series<Option<T>, U>, RudasError> {
+        self.zip_apply(other, true, |&a, &b| a.checked_add(b))
+    }
+
+    /// Element-wise product with `other`, `None` where it overflows `T`. Both
+    /// series must have the same labels.
+    pub fn checked_mul(&self, other: &Series<T, U>) -> Result<Series<Option<T>, U>, RudasError> {
+        self.zip_apply(other, true, |&a, &b| a.checked_mul(b))
+    }
+
+    /// `None` when the sum overflows `T`.
+    pub fn checked_sum(&self) -> Option<T> {
+        self.data
+            .iter()
+            .try_fold(T::zero(), |acc, &x| acc.checked_add(x))
+    }
+
+    /// `None` when the product overflows `T`.
+    pub fn checked_product(&self) -> Option<T> {
+        self.data
+            .iter()
+            .try_fold(T::one(), |acc, &x| acc.checked_mul(x))
+    }
+
+    /// Sums the values in the wider type `A`, e.g. `sum_as::<i128>()`.
+    pub fn sum_as<A: Numeric>(&self) -> A
+    where
+        T: Into<A>,
     {
-        for (i, v) in self.0.iter().enumerate() {
-            println!("{}\t{:}", i, v);
+        self.data.iter().fold(A::zero(), |acc, &x| acc + x.into())
+    }
+
+    pub fn sum_f64(&self) -> f64 {
+        self.data.iter().map(|x| x.to_f64()).sum()
+    }
+
+    /// Sums the values as `f64` with Kahan-Babuska compensated summation,
+    /// which keeps the low-order bits that a plain sum loses.
+    pub fn sum_kahan(&self) -> f64 {
+        let (mut sum, mut compensation) = (0.0f64, 0.0f64);
+        for x in self.data.iter().map(|x| x.to_f64()) {
+            let t = sum + x;
+            compensation += if sum.abs() >= x.abs() {
+                (sum - t) + x
+            } else {
+                (x - t) + sum
+            };
+            sum = t;
+        }
+        sum + compensation
+    }
+
+    pub fn cumsum(&self) -> Series<T, U> {
+        let mut acc = T::zero();
+        self.with_data(
+            self.data
+                .iter()
+                .map(|&x| {
+                    acc = acc + x;
+                    acc
+                })
+                .collect(),
+        )
+    }
+
+    /// `None` when a running sum overflows `T`.
+    pub fn checked_cumsum(&self) -> Option<Series<T, U>> {
+        let mut acc = T::zero();
+        let data = self
+            .data
+            .iter()
+            .map(|&x| {
+                acc = acc.checked_add(x)?;
+                Some(acc)
+            })
+            .collect::<Option<Vec<T>>>()?;
+        Some(self.with_data(data))
+    }
+
+    /// Returns `NAN` for an empty series.
+    pub fn mean(&self) -> f64 {
+        mean_of(&self.data)
+    }
+
+    /// Variance with `ddof` delta degrees of freedom. Returns `NAN` when
+    /// there are not more than `ddof` elements.
+    pub fn var(&self, ddof: usize) -> f64 {
+        var_of(&self.data, ddof)
+    }
+
+    pub fn std(&self, ddof: usize) -> f64 {
+        self.var(ddof).sqrt()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.data
+            .iter()
+            .copied()
+            .reduce(|a, b| if b < a { b } else { a })
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.data
+            .iter()
+            .copied()
+            .reduce(|a, b| if b > a { b } else { a })
+    }
+
+    /// The `q`-th quantile using linear interpolation between the closest
+    /// ranks. Returns `NAN` for an empty series or `q` outside `[0, 1]`.
+    pub fn quantile(&self, q: f64) -> f64 {
+        quantile_of_sorted(&self.sorted_f64(), q)
+    }
+
+    fn sorted_f64(&self) -> Vec<f64> {
+        let mut sorted: Vec<f64> = self.data.iter().map(|x| x.to_f64()).collect();
+        sorted.sort_by(f64::total_cmp);
+        sorted
+    }
+
+    /// Interquartile range, the 75% quantile minus the 25% quantile.
+    pub fn iqr(&self) -> f64 {
+        let sorted = self.sorted_f64();
+        quantile_of_sorted(&sorted, 0.75) - quantile_of_sorted(&sorted, 0.25)
+    }
+
+    /// Marks the values outside `[Q1 - k * IQR, Q3 + k * IQR]`, with `k`
+    /// usually 1.5.
+    pub fn outlier_mask(&self, k: f64) -> Series<bool, U> {
+        let sorted = self.sorted_f64();
+        let (q1, q3) = (
+            quantile_of_sorted(&sorted, 0.25),
+            quantile_of_sorted(&sorted, 0.75),
+        );
+        let (lower, upper) = (q1 - k * (q3 - q1), q3 + k * (q3 - q1));
+        self.map(|x| {
+            let x = x.to_f64();
+            x < lower || x > upper
+        })
+    }
+
+    /// Clips the values to the `lower_q` and `upper_q` quantiles.
+    pub fn winsorize(&self, lower_q: f64, upper_q: f64) -> Result<Series<f64, U>, RudasError> {
+        if !(0.0..=1.0).contains(&lower_q) || !(lower_q..=1.0).contains(&upper_q) {
+            return Err(RudasError::InvalidArgument(
+                "quantiles must satisfy 0 <= lower_q <= upper_q <= 1".to_string(),
+            ));
+        }
+        let sorted = self.sorted_f64();
+        let lower = quantile_of_sorted(&sorted, lower_q);
+        let upper = quantile_of_sorted(&sorted, upper_q);
+        Ok(self.scaled(|x| clip_of(x, lower, upper)))
+    }
+
+    /// Splits the values into `q` bins holding roughly the same number of
+    /// elements and returns the bin index `0..q` of each element.
+    ///
+    /// Bin edges are the `k / q` quantiles of the non-`NAN` values and a value
+    /// equal to an edge falls into the lower bin, so equal values always share
+    /// a bin. Heavily tied data can therefore leave some bins empty. `NAN`
+    /// values get no bin and become `None`.
+    pub fn qcut(&self, q: usize) -> Result<Series<Option<usize>, U>, RudasError> {
+        Ok(self.qcut_with_edges(q)?.0)
+    }
+
+    /// Same as `qcut`, also returning the range of every bin so that bin `i`
+    /// is `intervals[i]`. The first bin is closed on both ends at the minimum
+    /// and the others are `(lower, upper]`, the last ending at the maximum.
+    /// Without any non-`NAN` value there are no intervals.
+    pub fn qcut_with_edges(&self, q: usize) -> Result<QuantileBins<U>, RudasError> {
+        if q == 0 {
+            return Err(RudasError::InvalidArgument(
+                "q must be at least 1".to_string(),
+            ));
+        }
+        let mut sorted = self.sorted_f64();
+        sorted.retain(|x| !x.is_nan());
+        let edges: Vec<f64> = (1..q)
+            .map(|k| quantile_of_sorted(&sorted, k as f64 / q as f64))
+            .collect();
+        let bins = self.with_data(
+            self.data
+                .iter()
+                .map(|x| {
+                    let x = x.to_f64();
+                    (!x.is_nan()).then(|| edges.partition_point(|&edge| edge < x))
+                })
+                .collect(),
+        );
+        let intervals = match (sorted.first(), sorted.last()) {
+            (Some(&min), Some(&max)) => {
+                let bounds: Vec<f64> = std::iter::once(min)
+                    .chain(edges)
+                    .chain(std::iter::once(max))
+                    .collect();
+                bounds
+                    .windows(2)
+                    .enumerate()
+                    .map(|(i, w)| {
+                        let closed = if i == 0 { Closed::Both } else { Closed::Right };
+                        Interval::new(w[0], w[1], closed)
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        Ok((bins, intervals))
+    }
+
+    /// Summary statistics labeled `count`, `mean`, `std`, `min`, `25%`,
+    /// `50%`, `75%` and `max`. `std` uses `ddof = 1`.
+    pub fn describe(&self) -> Series<f64, String> {
+        let or_nan = |x: Option<T>| x.map_or(f64::NAN, |x| x.to_f64());
+        let stats = [
+            ("count", self.len() as f64),
+            ("mean", self.mean()),
+            ("std", self.std(1)),
+            ("min", or_nan(self.min())),
+            ("25%", self.quantile(0.25)),
+            ("50%", self.quantile(0.5)),
+            ("75%", self.quantile(0.75)),
+            ("max", or_nan(self.max())),
+        ];
+        Series {
+            name: self.name.clone(),
+            ..Series::from_parts(
+                stats.iter().map(|(_, v)| *v).collect(),
+                stats.iter().map(|(l, _)| l.to_string()).collect(),
+            )
+        }
+    }
+
+    /// Counts the values in `bins` equal-width buckets spanning `[min, max]`,
+    /// labeled by their range. Every bucket includes its lower edge and the
+    /// last one also its upper edge. A constant series uses the range
+    /// `[x - 0.5, x + 0.5]`, an empty one gives no buckets and `NAN` values
+    /// are not counted.
+    pub fn histogram(&self, bins: usize) -> Result<Series<usize, Interval<f64>>, RudasError> {
+        if bins == 0 {
+            return Err(RudasError::InvalidArgument(
+                "bins must be at least 1".to_string(),
+            ));
+        }
+        let values: Vec<f64> = self
+            .data
+            .iter()
+            .map(|x| x.to_f64())
+            .filter(|x| !x.is_nan())
+            .collect();
+        if values.is_empty() {
+            return Ok(Series::from_parts(Vec::new(), Vec::new()));
+        }
+        let (mut min, mut max) = values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| {
+                (lo.min(x), hi.max(x))
+            });
+        if min == max {
+            min -= 0.5;
+            max += 0.5;
+        }
+        let width = (max - min) / bins as f64;
+        let mut counts = vec![0; bins];
+        for x in values {
+            counts[(((x - min) / width) as usize).min(bins - 1)] += 1;
+        }
+        let edge = |i: usize| min + width * i as f64;
+        let mut label: Vec<Interval<f64>> = (0..bins)
+            .map(|i| Interval::new(edge(i), edge(i + 1), Closed::Left))
+            .collect();
+        label[bins - 1].upper = max;
+        label[bins - 1].closed = Closed::Both;
+        Ok(Series::from_parts(counts, label))
+    }
+
+    /// Renders the values as a line of block characters scaled between the
+    /// minimum and maximum. A constant series renders at the middle level and
+    /// `NAN` values render as spaces.
+    pub fn sparkline(&self) -> String {
+        const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        let values: Vec<f64> = self.data.iter().map(|x| x.to_f64()).collect();
+        let (min, max) = values
+            .iter()
+            .filter(|x| !x.is_nan())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| {
+                (lo.min(x), hi.max(x))
+            });
+        values
+            .iter()
+            .map(|&x| {
+                if x.is_nan() {
+                    ' '
+                } else if max == min {
+                    LEVELS[3]
+                } else {
+                    LEVELS[((x - min) / (max - min) * 7.0).round() as usize]
+                }
+            })
+            .collect()
+    }
+
+    /// Assigns every value to the bin `(bins[i], bins[i + 1]]` and emits
+    /// `labels[i]`. Values outside every bin, and `NAN`, become `None`.
+    /// `bins` must be strictly increasing and there must be one label per bin.
+    pub fn cut(&self, bins: &[f64], labels: &[U]) -> Result<Series<Option<U>, U>, RudasError> {
+        check_bins(bins)?;
+        if labels.len() != bins.len() - 1 {
+            return Err(RudasError::LengthMismatch {
+                expected: bins.len() - 1,
+                found: labels.len(),
+            });
+        }
+        Ok(self.map(|x| bin_of(bins, x.to_f64()).map(|i| labels[i].clone())))
+    }
+
+    /// Same as `cut`, emitting the bin itself as an `Interval` closed on the
+    /// right.
+    pub fn cut_intervals(
+        &self,
+        bins: &[f64],
+    ) -> Result<Series<Option<Interval<f64>>, U>, RudasError> {
+        check_bins(bins)?;
+        Ok(self.map(|x| {
+            bin_of(bins, x.to_f64()).map(|i| Interval::new(bins[i], bins[i + 1], Closed::Right))
+        }))
+    }
+
+    /// Limits the values to `[lower, upper]`.
+    pub fn clip(&self, lower: T, upper: T) -> Series<T, U> {
+        self.map(|&x| clip_of(x, lower, upper))
+    }
+
+    /// Standardizes the values to zero mean and unit standard deviation.
+    /// Every value is `NAN` when the standard deviation is zero.
+    pub fn zscore(&self, ddof: usize) -> Series<f64, U> {
+        let (mean, std) = (self.mean(), self.std(ddof));
+        self.scaled(|x| {
+            if std == 0.0 {
+                f64::NAN
+            } else {
+                (x - mean) / std
+            }
+        })
+    }
+
+    /// Maps the values linearly onto `[0, 1]`. Every value is `NAN` when the
+    /// series is constant.
+    pub fn minmax_scale(&self) -> Series<f64, U> {
+        let values = self.data.iter().map(|x| x.to_f64());
+        let min = values.clone().fold(f64::INFINITY, f64::min);
+        let max = values.fold(f64::NEG_INFINITY, f64::max);
+        self.scaled(|x| {
+            if max == min {
+                f64::NAN
+            } else {
+                (x - min) / (max - min)
+            }
+        })
+    }
+
+    fn scaled(&self, f: impl Fn(f64) -> f64) -> Series<f64, U> {
+        self.with_data(self.data.iter().map(|x| f(x.to_f64())).collect())
+    }
+
+    /// `sum(value * weight) / sum(weight)`. Both series must have the same
+    /// labels and the weights must not sum to zero.
+    pub fn weighted_mean<V: Numeric>(&self, weights: &Series<V, U>) -> Result<f64, RudasError> {
+        self.check_zip(weights, true)?;
+        let (total, weight) = self
+            .data
+            .iter()
+            .zip(&weights.data)
+            .fold((0.0, 0.0), |(total, weight), (x, w)| {
+                (total + x.to_f64() * w.to_f64(), weight + w.to_f64())
+            });
+        if weight == 0.0 {
+            return Err(RudasError::InvalidArgument(
+                "weights must not sum to zero".to_string(),
+            ));
+        }
+        Ok(total / weight)
+    }
+
+    /// Pearson correlation between the series and itself shifted by `lag`,
+    /// over the overlapping values. Returns `NAN` when fewer than two values
+    /// overlap or either side has zero variance.
+    pub fn autocorr(&self, lag: usize) -> f64 {
+        let values: Vec<Option<f64>> = self.data.iter().map(|x| Some(x.to_f64())).collect();
+        let n = values.len().saturating_sub(lag);
+        pearson(&values[..n], &values[values.len() - n..])
+    }
+
+    /// Spearman rank correlation with `other`, the Pearson correlation of
+    /// their `rank`s. Both series must have the same labels. Pairs where
+    /// either value is `NAN` are left out before ranking, and the result is
+    /// `NAN` when fewer than two pairs remain or either side is constant.
+    pub fn spearman_corr<V: Numeric>(&self, other: &Series<V, U>) -> Result<f64, RudasError> {
+        self.check_zip(other, true)?;
+        let (x, y): (Vec<f64>, Vec<f64>) = self
+            .data
+            .iter()
+            .zip(&other.data)
+            .map(|(a, b)| (a.to_f64(), b.to_f64()))
+            .filter(|(a, b)| !a.is_nan() && !b.is_nan())
+            .unzip();
+        let ranks = |values: &[f64]| -> Vec<Option<f64>> {
+            Series::from(values)
+                .rank()
+                .data
+                .into_iter()
+                .map(Some)
+                .collect()
+        };
+        Ok(pearson(&ranks(&x), &ranks(&y)))
+    }
+
+    /// Pearson correlation with `other` over every trailing window of
+    /// `window` pairs, in O(n) using running co-moments. Both series must
+    /// have the same labels. The first `window - 1` positions are `None` and
+    /// windows where either side has zero variance are `NAN`.
+    pub fn rolling_corr<V: Numeric>(
+        &self,
+        other: &Series<V, U>,
+        window: usize,
+    ) -> Result<Series<Option<f64>, U>, RudasError> {
+        self.rolling_pair(other, window, CoMoments::corr)
+    }
+
+    /// Sample covariance with `other` over every trailing window of `window`
+    /// pairs, in O(n). Both series must have the same labels. The first
+    /// `window - 1` positions are `None` and every window is `NAN` when
+    /// `window` is 1.
+    pub fn rolling_cov<V: Numeric>(
+        &self,
+        other: &Series<V, U>,
+        window: usize,
+    ) -> Result<Series<Option<f64>, U>, RudasError> {
+        self.rolling_pair(other, window, CoMoments::cov)
+    }
+
+    fn rolling_pair<V: Numeric>(
+        &self,
+        other: &Series<V, U>,
+        window: usize,
+        stat: fn(&CoMoments) -> f64,
+    ) -> Result<Series<Option<f64>, U>, RudasError> {
+        if window == 0 {
+            return Err(RudasError::InvalidArgument(
+                "window must be at least 1".to_string(),
+            ));
+        }
+        self.check_zip(other, true)?;
+        let x: Vec<f64> = self.data.iter().map(|v| v.to_f64()).collect();
+        let y: Vec<f64> = other.data.iter().map(|v| v.to_f64()).collect();
+        let mut moments = CoMoments::default();
+        let mut data = Vec::with_capacity(self.len());
+        for i in 0..x.len() {
+            if i >= window && (i + 1) % window == 0 {
+                // Rebuild from the window now and then so that rounding from
+                // the updates cannot accumulate; this stays O(1) amortized.
+                moments = CoMoments::default();
+                for j in i + 1 - window..=i {
+                    moments.add(x[j], y[j]);
+                }
+            } else {
+                moments.add(x[i], y[i]);
+                if i >= window {
+                    moments.remove(x[i - window], y[i - window]);
+                }
+            }
+            data.push((i + 1 >= window).then(|| stat(&moments)));
+        }
+        Ok(self.with_data(data))
+    }
+
+    pub fn rolling_mean(&self, window: usize) -> Result<Series<Option<f64>, U>, RudasError> {
+        self.rolling(window, mean_of)
+    }
+
+    /// Mean of every trailing window of `weights.len()` elements, weighting
+    /// the oldest value with `weights[0]` and dividing by the weight sum.
+    /// Fails when `weights` is empty or sums to zero.
+    pub fn rolling_weighted_mean(
+        &self,
+        weights: &[f64],
+    ) -> Result<Series<Option<f64>, U>, RudasError> {
+        let total: f64 = weights.iter().sum();
+        if weights.is_empty() || total == 0.0 {
+            return Err(RudasError::InvalidArgument(
+                "weights must not be empty or sum to zero".to_string(),
+            ));
+        }
+        self.rolling(weights.len(), |w| {
+            w.iter()
+                .zip(weights)
+                .map(|(x, weight)| x.to_f64() * weight)
+                .sum::<f64>()
+                / total
+        })
+    }
+
+    /// Median of every trailing window of `window` elements. Every window is
+    /// copied and sorted, so this takes O(n * window * log(window)) time.
+    pub fn rolling_median(&self, window: usize) -> Result<Series<Option<f64>, U>, RudasError> {
+        self.rolling(window, median_of)
+    }
+
+    pub fn rolling_std(
+        &self,
+        window: usize,
+        ddof: usize,
+    ) -> Result<Series<Option<f64>, U>, RudasError> {
+        self.rolling(window, |w| var_of(w, ddof).sqrt())
+    }
+}
+
+impl<U: PartialEq + Clone> Not for Series<bool, U> {
+    type Output = Series<bool, U>;
+
+    fn not(mut self) -> Series<bool, U> {
+        self.data.iter_mut().for_each(|x| *x = !*x);
+        self
+    }
+}
+
+impl<U: PartialEq + Clone> Not for &Series<bool, U> {
+    type Output = Series<bool, U>;
+
+    fn not(self) -> Series<bool, U> {
+        !self.clone()
+    }
+}
+
+impl<T: Clone + Debug, U: PartialEq + Clone + Debug> Debug for Series<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Series");
+        if let Some(name) = &self.name {
+            debug.field("name", name);
+        }
+        if let Some(index_name) = &self.index_name {
+            debug.field("index_name", index_name);
+        }
+        debug
+            .field("len", &self.len())
+            .field("data", &Preview(&self.data))
+            .field("label", &Preview(&self.label))
+            .finish()
+    }
+}
+
+/// The first `DEBUG_PREVIEW` elements of a slice, followed by `...` when
+/// there are more.
+struct Preview<'a, T>(&'a [T]);
+
+impl<T: Debug> Debug for Preview<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        list.entries(self.0.iter().take(DEBUG_PREVIEW));
+        if self.0.len() > DEBUG_PREVIEW {
+            list.entry(&format_args!("..."));
+        }
+        list.finish()
+    }
+}
+
+impl<T: Clone, U: PartialEq + Clone> Default for Series<T, U> {
+    fn default() -> Series<T, U> {
+        Series::from_parts(Vec::new(), Vec::new())
+    }
+}
+
+/// Appends `(label, value)` pairs.
+impl<T: Clone, U: PartialEq + Clone> Extend<(U, T)> for Series<T, U> {
+    fn extend<I: IntoIterator<Item = (U, T)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.data.reserve(lower);
+        self.label.reserve(lower);
+        for (label, value) in iter {
+            self.label.push(label);
+            self.data.push(value);
+        }
+    }
+}
+
+/// Builds a series from owned `(data, label)` vectors without copying them.
+/// Fails when the lengths differ.
+impl<T: Clone, U: PartialEq + Clone> TryFrom<(Vec<T>, Vec<U>)> for Series<T, U> {
+    type Error = RudasError;
+
+    fn try_from((data, label): (Vec<T>, Vec<U>)) -> Result<Series<T, U>, RudasError> {
+        if data.len() != label.len() {
+            return Err(RudasError::LengthMismatch {
+                expected: label.len(),
+                found: data.len(),
+            });
+        }
+        Ok(Series::from_parts(data, label))
+    }
+}
+
+/// Pairs the values of two series with the same labels.
+pub fn zip2<A: Clone, B: Clone, U: PartialEq + Clone>(
+    a: &Series<A, U>,
+    b: &Series<B, U>,
+) -> Result<Series<(A, B), U>, RudasError> {
+    a.zip_apply(b, true, |x, y| (x.clone(), y.clone()))
+}
+
+/// Groups the values of three series with the same labels into triples.
+pub fn zip3<A: Clone, B: Clone, C: Clone, U: PartialEq + Clone>(
+    a: &Series<A, U>,
+    b: &Series<B, U>,
+    c: &Series<C, U>,
+) -> Result<Series<(A, B, C), U>, RudasError> {
+    a.zip_apply3(b, c, true, |x, y, z| (x.clone(), y.clone(), z.clone()))
+}
+
+/// A borrowed range of a `Series`, returned by `Series::view`.
+#[derive(Debug, Clone, Copy)]
+pub struct SeriesView<'a, T, U = usize> {
+    data: &'a [T],
+    label: &'a [U],
+}
+
+impl<'a, T: Clone, U: PartialEq + Clone> SeriesView<'a, T, U> {
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn data(&self) -> &'a [T] {
+        self.data
+    }
+
+    pub fn label(&self) -> &'a [U] {
+        self.label
+    }
+
+    /// The label and value at position `i` of the view.
+    pub fn get(&self, i: usize) -> Option<(&'a U, &'a T)> {
+        Some((self.label.get(i)?, self.data.get(i)?))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'a U, &'a T)> {
+        self.label.iter().zip(self.data)
+    }
+
+    /// Copies the viewed elements into a new series.
+    pub fn to_series(&self) -> Series<T, U> {
+        Series::from_label(self.data, self.label)
+    }
+}
+
+/// Hashed label positions of a `Series`, returned by `Series::build_index`.
+/// Duplicate labels resolve to their first occurrence.
+pub struct LabelIndex<'a, T: Clone, U: PartialEq + Clone> {
+    series: &'a Series<T, U>,
+    positions: HashMap<&'a U, usize>,
+}
+
+impl<'a, T: Clone, U: PartialEq + Clone + Hash + Eq> LabelIndex<'a, T, U> {
+    pub fn get_loc(&self, label: &U) -> Option<usize> {
+        self.positions.get(label).copied()
+    }
+
+    pub fn loc(&self, label: &U) -> Option<&'a T> {
+        let series = self.series;
+        self.get_loc(label).map(|i| &series.data[i])
+    }
+
+    pub fn contains(&self, label: &U) -> bool {
+        self.positions.contains_key(label)
+    }
+}
+
+/// Labels of a `Series` known to be sorted, returned by
+/// `Series::sorted_index`. Lookups take O(log n) and duplicate labels
+/// resolve to their first occurrence.
+pub struct SortedIndex<'a, T: Clone, U: PartialEq + Clone> {
+    series: &'a Series<T, U>,
+}
+
+impl<'a, T: Clone, U: PartialEq + Clone + PartialOrd> SortedIndex<'a, T, U> {
+    pub fn get_loc(&self, label: &U) -> Option<usize> {
+        let i = self.series.label.partition_point(|l| l < label);
+        (self.series.label.get(i) == Some(label)).then_some(i)
+    }
+
+    pub fn loc(&self, label: &U) -> Option<&'a T> {
+        let series = self.series;
+        self.get_loc(label).map(|i| &series.data[i])
+    }
+
+    pub fn contains(&self, label: &U) -> bool {
+        self.get_loc(label).is_some()
+    }
+
+    /// Elements whose labels lie in `[start, end]`.
+    pub fn slice_by_label_range(&self, start: &U, end: &U) -> Series<T, U> {
+        let series = self.series;
+        let lo = series.label.partition_point(|l| l < start);
+        let hi = series.label.partition_point(|l| l <= end).max(lo);
+        Series::from_label(&series.data[lo..hi], &series.label[lo..hi]).with_names_of(series)
+    }
+}
+
+type LabelCheck<U> = fn(&[U]) -> Result<(), RudasError>;
+
+/// The bins and bin ranges of `Series::qcut_with_edges`.
+type QuantileBins<U> = (Series<Option<usize>, U>, Vec<Interval<f64>>);
+
+/// Collects labeled values one by one into a `Series`.
+pub struct SeriesBuilder<T: Clone, U: PartialEq + Clone = usize> {
+    data: Vec<T>,
+    label: Vec<U>,
+    check: Option<LabelCheck<U>>,
+}
+
+impl<T: Clone, U: PartialEq + Clone> SeriesBuilder<T, U> {
+    pub fn new() -> SeriesBuilder<T, U> {
+        SeriesBuilder::with_capacity(0)
+    }
+
+    pub fn with_capacity(n: usize) -> SeriesBuilder<T, U> {
+        SeriesBuilder {
+            data: Vec::with_capacity(n),
+            label: Vec::with_capacity(n),
+            check: None,
+        }
+    }
+
+    pub fn push(mut self, label: U, value: T) -> SeriesBuilder<T, U> {
+        self.data.push(value);
+        self.label.push(label);
+        self
+    }
+
+    /// Makes `build` fail when a label was pushed more than once.
+    pub fn require_unique(mut self) -> SeriesBuilder<T, U>
+    where
+        U: Hash + Eq + Display,
+    {
+        self.check = Some(check_unique::<U>);
+        self
+    }
+
+    pub fn build(self) -> Result<Series<T, U>, RudasError> {
+        if let Some(check) = self.check {
+            check(&self.label)?;
+        }
+        Ok(Series::from_parts(self.data, self.label))
+    }
+}
+
+impl<T: Clone, U: PartialEq + Clone> Default for SeriesBuilder<T, U> {
+    fn default() -> SeriesBuilder<T, U> {
+        SeriesBuilder::new()
+    }
+}
+
+fn check_bins(bins: &[f64]) -> Result<(), RudasError> {
+    if bins.len() < 2 || !bins.windows(2).all(|w| w[0] < w[1]) {
+        return Err(RudasError::InvalidArgument(
+            "bins must be at least two strictly increasing edges".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Index of the bin `(bins[i], bins[i + 1]]` holding `x`.
+fn bin_of(bins: &[f64], x: f64) -> Option<usize> {
+    let i = bins.partition_point(|&edge| edge < x);
+    (i > 0 && i < bins.len()).then(|| i - 1)
+}
+
+fn check_unique<U: Hash + Eq + Display>(labels: &[U]) -> Result<(), RudasError> {
+    let mut seen = HashSet::with_capacity(labels.len());
+    match labels.iter().find(|l| !seen.insert(*l)) {
+        Some(label) => Err(RudasError::DuplicateEntry(label.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// Whether `x` does not compare with itself, like `NAN`.
+fn is_unordered<T: PartialOrd>(x: &T) -> bool {
+    x.partial_cmp(x).is_none()
+}
+
+pub(crate) fn clip_of<T: Numeric>(x: T, lower: T, upper: T) -> T {
+    if x < lower {
+        lower
+    } else if x > upper {
+        upper
+    } else {
+        x
+    }
+}
+
+/// Means and co-moments of a window of `(x, y)` pairs, updated as pairs enter
+/// and leave so that each step costs O(1).
+#[derive(Default)]
+struct CoMoments {
+    n: f64,
+    mean_x: f64,
+    mean_y: f64,
+    m2_x: f64,
+    m2_y: f64,
+    c_xy: f64,
+}
+
+impl CoMoments {
+    fn add(&mut self, x: f64, y: f64) {
+        self.n += 1.0;
+        let dx = x - self.mean_x;
+        let dy = y - self.mean_y;
+        self.mean_x += dx / self.n;
+        self.mean_y += dy / self.n;
+        self.m2_x += dx * (x - self.mean_x);
+        self.m2_y += dy * (y - self.mean_y);
+        self.c_xy += dx * (y - self.mean_y);
+    }
+
+    fn remove(&mut self, x: f64, y: f64) {
+        let dx = x - self.mean_x;
+        let dy = y - self.mean_y;
+        self.n -= 1.0;
+        if self.n == 0.0 {
+            *self = CoMoments::default();
+            return;
+        }
+        self.mean_x -= dx / self.n;
+        self.mean_y -= dy / self.n;
+        self.m2_x -= dx * (x - self.mean_x);
+        self.m2_y -= dy * (y - self.mean_y);
+        self.c_xy -= dx * (y - self.mean_y);
+    }
+
+    fn cov(&self) -> f64 {
+        if self.n > 1.0 {
+            self.c_xy / (self.n - 1.0)
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// `NAN` when either side has zero variance, allowing for the rounding
+    /// left behind by removed pairs.
+    fn corr(&self) -> f64 {
+        let flat = |m2: f64, mean: f64| m2 <= 16.0 * self.n * f64::EPSILON * mean * mean;
+        if flat(self.m2_x, self.mean_x) || flat(self.m2_y, self.mean_y) {
+            return f64::NAN;
+        }
+        (self.c_xy / (self.m2_x * self.m2_y).sqrt()).clamp(-1.0, 1.0)
+    }
+}
+
+fn mean_of<T: Numeric>(values: &[T]) -> f64 {
+    if values.is_empty() {
+        return f64::NAN;
+    }
+    values.iter().map(|x| x.to_f64()).sum::<f64>() / values.len() as f64
+}
+
+fn median_of<T: Numeric>(values: &[T]) -> f64 {
+    let mut sorted: Vec<f64> = values.iter().map(|x| x.to_f64()).collect();
+    sorted.sort_by(f64::total_cmp);
+    quantile_of_sorted(&sorted, 0.5)
+}
+
+fn quantile_of_sorted(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() || !(0.0..=1.0).contains(&q) {
+        return f64::NAN;
+    }
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+fn var_of<T: Numeric>(values: &[T], ddof: usize) -> f64 {
+    if values.len() <= ddof {
+        return f64::NAN;
+    }
+    let mean = mean_of(values);
+    let ss: f64 = values.iter().map(|x| (x.to_f64() - mean).powi(2)).sum();
+    ss / (values.len() - ddof) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived_series_keep_names() {
+        let s = Series::from_label(&[3, 1, 2], &["a", "b", "c"])
+            .with_name("n")
+            .with_index_name("key");
+        fn names<T: Clone, U: PartialEq + Clone>(
+            s: &Series<T, U>,
+        ) -> (Option<String>, Option<String>) {
+            (s.name.clone(), s.index_name.clone())
+        }
+        let both = (Some("n".to_string()), Some("key".to_string()));
+        assert_eq!(names(&s.map(|v| v * 2)), both);
+        assert_eq!(names(&s.filter_by(|&v| v > 1)), both);
+        assert_eq!(names(&s.shift(1)), both);
+        assert_eq!(names(&s.cumsum()), both);
+        assert_eq!(names(&s.relabel_with(|l| l.len())), both);
+        assert_eq!(names(&Series::from(&[1])), (None, None));
+    }
+
+    #[test]
+    fn entropy_of_uniform_and_constant_series() {
+        let uniform = Series::from(&[1, 2, 3, 4, 1, 2, 3, 4]);
+        assert!((uniform.entropy() - 2.0).abs() < 1e-12);
+        let constant = Series::from(&[7; 5]);
+        assert!(constant.entropy().is_sign_positive());
+        assert_eq!(constant.entropy(), 0.0);
+        let empty: Series<i32> = Series::from(&[]);
+        assert!(empty.entropy().is_sign_positive());
+        assert_eq!(empty.entropy(), 0.0);
+    }
+
+    fn runs_of<T: Clone + PartialEq, U: PartialEq + Clone>(s: &Series<T, U>) -> Vec<(T, usize)> {
+        s.run_lengths()
+            .into_iter()
+            .map(|(value, n, _)| (value, n))
+            .collect()
+    }
+
+    #[test]
+    fn run_lengths_of_mixed_series() {
+        let s = Series::from_label(&["on", "on", "off", "on"], &[10, 11, 12, 13]);
+        assert_eq!(s.changed().data, vec![true, false, true, true]);
+        assert_eq!(
+            s.run_lengths(),
+            vec![("on", 2, 10), ("off", 1, 12), ("on", 1, 13)]
+        );
+        assert!(Series::<i32>::from(&[]).changed().is_empty());
+        assert!(Series::<i32>::from(&[]).run_lengths().is_empty());
+    }
+
+    #[test]
+    fn run_lengths_of_constant_series() {
+        let s = Series::from(&[5, 5, 5]);
+        assert_eq!(s.run_lengths(), vec![(5, 3, 0)]);
+        assert_eq!(s.changed().data, vec![true, false, false]);
+        assert_eq!(Series::from_runs(&runs_of(&s)).data, s.data);
+    }
+
+    #[test]
+    fn run_lengths_of_alternating_series() {
+        let s = Series::from(&[1, 2, 1, 2]);
+        assert!(s.run_lengths().iter().all(|(_, n, _)| *n == 1));
+        assert!(s.changed().data.iter().all(|&c| c));
+        let back = Series::from_runs(&runs_of(&s));
+        assert_eq!(back.data, s.data);
+        assert_eq!(back.label, s.label);
+    }
+
+    #[test]
+    fn from_runs_round_trips_values() {
+        let s = Series::from_label(
+            &["a", "a", "b", "c", "c", "c"],
+            &["p", "q", "r", "s", "t", "u"],
+        );
+        let back = Series::from_runs(&runs_of(&s));
+        assert_eq!(back.data, s.data);
+        assert_eq!(back.label, (0..6).collect::<Vec<_>>());
+        assert!(Series::<i32>::from_runs(&[(1, 0)]).is_empty());
+    }
+
+    #[test]
+    fn spearman_corr_sees_monotonic_relations() {
+        let x = Series::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let y = x.map(|v: &f64| v.exp());
+        assert!((x.spearman_corr(&y).unwrap() - 1.0).abs() < 1e-12);
+        let z = Series::from(&[5, 4, 3, 2, 1]);
+        assert!((x.spearman_corr(&z).unwrap() + 1.0).abs() < 1e-12);
+        assert!(x.spearman_corr(&Series::from(&[1.0; 5])).unwrap().is_nan());
+        let w = Series::from(&[1.0, f64::NAN, 3.0, 2.0, 5.0]);
+        assert!((x.spearman_corr(&w).unwrap() - 0.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn spearman_corr_requires_aligned_labels() {
+        let x = Series::from_label(&[1.0, 2.0, 3.0], &["x", "y", "z"]);
+        let y = Series::from_label(&[1.0, 2.0, 3.0], &["p", "q", "r"]);
+        assert!(x.spearman_corr(&y).is_err());
+        assert!(x.rolling_corr(&y, 2).is_err());
+        let short = Series::from_label(&[1.0, 2.0], &["x", "y"]);
+        assert!(x.spearman_corr(&short).is_err());
+    }
+
+    #[test]
+    fn clone_copies_nested_data() {
+        let s = Series::from_label(&[vec![1, 2], vec![3]], &["a".to_string(), "b".to_string()]);
+        let mut c = s.clone();
+        c.data[0].push(9);
+        c.data[1].clear();
+        c.label[1].push('x');
+        assert_eq!(s.data, vec![vec![1, 2], vec![3]]);
+        assert_eq!(s.label, vec!["a", "b"]);
+        assert_eq!(c.data, vec![vec![1, 2, 9], vec![]]);
+    }
+
+    #[test]
+    fn sorted_index_searches_labels() {
+        let s = Series::from_label(&[1, 2, 3, 4, 5], &[10, 20, 20, 30, 40]);
+        let index = s.sorted_index().unwrap();
+        assert_eq!(index.get_loc(&20), Some(1));
+        assert_eq!(index.get_loc(&25), None);
+        assert_eq!(index.loc(&40), Some(&5));
+        assert!(!index.contains(&5));
+        let range = index.slice_by_label_range(&15, &30);
+        assert_eq!(range.label, vec![20, 20, 30]);
+        assert_eq!(range.data, vec![2, 3, 4]);
+        assert!(index.slice_by_label_range(&41, &50).is_empty());
+        assert!(index.slice_by_label_range(&30, &10).is_empty());
+    }
+
+    #[test]
+    fn label_range_requires_sorted_labels() {
+        let s = Series::from_label(&[1, 2, 3], &["b", "a", "c"]);
+        assert!(s.sorted_index().is_err());
+        let s = Series::from_label(&[1, 2, 3], &["a", "b", "c"]).with_name("n");
+        let range = s.slice_by_label_range(&"b", &"z");
+        assert_eq!(range.data, vec![2, 3]);
+        assert_eq!(range.name.as_deref(), Some("n"));
+        assert!(s.slice_by_label_range(&"c", &"a").is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "labels must be sorted")]
+    #[cfg(debug_assertions)]
+    fn label_range_on_unsorted_labels_panics_in_debug_builds() {
+        Series::from_label(&[1, 2], &[2, 1]).slice_by_label_range(&0, &5);
+    }
+
+    #[test]
+    fn rank_averages_ties() {
+        let s = Series::from(&[30, 10, 20, 10]);
+        assert_eq!(s.rank().data, vec![4.0, 1.5, 3.0, 1.5]);
+        assert_eq!(s.pct_rank().data, vec![1.0, 0.375, 0.75, 0.375]);
+        assert!(Series::<i32>::from(&[]).pct_rank().is_empty());
+    }
+
+    #[test]
+    fn rank_leaves_nan_unranked() {
+        let s = Series::from(&[2.0, f64::NAN, 1.0, 2.0, f64::NAN]);
+        let ranks = s.rank().data;
+        assert_eq!((ranks[0], ranks[2], ranks[3]), (2.5, 1.0, 2.5));
+        assert!(ranks[1].is_nan() && ranks[4].is_nan());
+        let pct = s.pct_rank().data;
+        assert_eq!((pct[0], pct[2]), (2.5 / 3.0, 1.0 / 3.0));
+        assert!(pct[1].is_nan());
+    }
+
+    #[test]
+    fn rank_does_not_panic_on_many_nans() {
+        let mut state = 7u64;
+        for _ in 0..300 {
+            let data: Vec<f64> = (0..40)
+                .map(|_| {
+                    state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                    match state >> 60 {
+                        0..=3 => f64::NAN,
+                        x => x as f64,
+                    }
+                })
+                .collect();
+            let ranks = Series::from(&data).rank().data;
+            for (x, r) in data.iter().zip(&ranks) {
+                assert_eq!(x.is_nan(), r.is_nan());
+            }
+        }
+    }
+
+    #[test]
+    fn quantile_interpolates_and_std_uses_ddof() {
+        let s = Series::from(&[1, 2, 3, 4]);
+        assert_eq!(s.quantile(0.25), 1.75);
+        assert_eq!(s.std(0), 1.25f64.sqrt());
+    }
+
+    #[test]
+    fn sparkline_scales_to_the_range() {
+        assert_eq!(
+            Series::from(&[1, 2, 3, 4, 5, 6, 7, 8]).sparkline(),
+            "▁▂▃▄▅▆▇█"
+        );
+        assert_eq!(Series::<f64>::from(&[]).sparkline(), "");
+        assert_eq!(Series::from(&[2, 2]).sparkline(), "▄▄");
+    }
+
+    #[test]
+    fn rolling_windows_start_after_the_first_full_window() {
+        let s = Series::from(&[1, 2, 3, 4]);
+        assert_eq!(
+            s.rolling_mean(2).unwrap().data,
+            vec![None, Some(1.5), Some(2.5), Some(3.5)]
+        );
+        assert_eq!(
+            s.rolling_std(2, 0).unwrap().data,
+            vec![None, Some(0.5), Some(0.5), Some(0.5)]
+        );
+        assert_eq!(
+            s.rolling(3, |w| (w[w.len() - 1] - w[0]) as f64)
+                .unwrap()
+                .data,
+            vec![None, None, Some(2.0), Some(2.0)]
+        );
+        assert_eq!(s.rolling_mean(9).unwrap().data, vec![None; 4]);
+        assert!(matches!(
+            s.rolling(0, |_| 0.0),
+            Err(RudasError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn shift_fill_moves_both_ways() {
+        let s = Series::from_label(&[1, 2, 3], &["a", "b", "c"]);
+        assert_eq!(s.shift_fill(1, 0).data, vec![0, 1, 2]);
+        assert_eq!(s.shift_fill(-2, 9).data, vec![3, 9, 9]);
+        assert_eq!(s.shift_fill(5, 0).data, vec![0, 0, 0]);
+        assert_eq!(s.shift_fill(-5, 0).data, vec![0, 0, 0]);
+        assert_eq!(s.shift_fill(0, 0).data, s.data);
+        assert_eq!(s.shift(-1).data, vec![Some(2), Some(3), None]);
+        assert_eq!(s.shift_fill(-1, 0).label, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn cut_assigns_right_closed_bins() {
+        let s = Series::from_label(
+            &[0.0, 0.5, 1.0, 1.5, 3.0, f64::NAN],
+            &["a", "b", "c", "d", "e", "f"],
+        );
+        let binned = s.cut(&[0.0, 1.0, 2.0], &["lo", "hi"]).unwrap();
+        assert_eq!(
+            binned.data,
+            vec![None, Some("lo"), Some("lo"), Some("hi"), None, None]
+        );
+        assert_eq!(binned.label, s.label);
+        assert!(s.cut(&[1.0, 0.0], &["x"]).is_err());
+        assert!(s.cut(&[0.0, 1.0], &["x", "y"]).is_err());
+    }
+
+    #[test]
+    fn qcut_splits_into_equal_frequency_bins() {
+        let s = Series::from(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let bins =
+            |s: Series<Option<usize>>| s.data.into_iter().map(Option::unwrap).collect::<Vec<_>>();
+        assert_eq!(bins(s.qcut(4).unwrap()), vec![0, 0, 1, 1, 2, 2, 3, 3]);
+        assert_eq!(bins(s.qcut(1).unwrap()), vec![0; 8]);
+        let tied = Series::from(&[1, 1, 1, 1, 2]);
+        assert_eq!(bins(tied.qcut(2).unwrap()), vec![0, 0, 0, 0, 1]);
+        assert!(s.qcut(0).is_err());
+    }
+
+    #[test]
+    fn qcut_ignores_nan_when_placing_edges() {
+        let s = Series::from(&[1.0, 2.0, 3.0, f64::NAN, f64::NAN, f64::NAN, 4.0]);
+        assert_eq!(
+            s.qcut(2).unwrap().data,
+            vec![Some(0), Some(0), Some(1), None, None, None, Some(1)]
+        );
+        let all_nan = Series::from(&[f64::NAN, f64::NAN]);
+        assert_eq!(all_nan.qcut(3).unwrap().data, vec![None, None]);
+    }
+
+    #[test]
+    fn parts_return_values_and_labels() {
+        let s = Series::from_label(&[1, 2], &["a", "b"]);
+        assert_eq!(s.as_parts(), (&[1, 2][..], &["a", "b"][..]));
+        assert_eq!(s.into_parts(), (vec![1, 2], vec!["a", "b"]));
+    }
+
+    #[test]
+    fn count_value_counts_equal_values() {
+        let s = Series::from(&[1.0, 2.0, 1.0, f64::NAN]);
+        assert_eq!(s.count_value(&1.0), 2);
+        assert_eq!(s.count_value(&3.0), 0);
+        assert_eq!(s.count_value(&f64::NAN), 0);
+    }
+
+    #[test]
+    fn monotonic_checks_allow_ties() {
+        let s = Series::from(&[1, 2, 2, 5]);
+        assert!(s.is_monotonic_increasing());
+        assert!(!s.is_monotonic_decreasing());
+        let s = Series::from(&[3.0, 3.0, -1.0]);
+        assert!(s.is_monotonic_decreasing());
+        assert!(!s.is_monotonic_increasing());
+        let empty: Series<i32> = Series::from(&[]);
+        assert!(empty.is_monotonic_increasing() && empty.is_monotonic_decreasing());
+    }
+
+    #[test]
+    fn zscore_and_minmax_scale() {
+        let s = Series::from(&[1, 2, 3]);
+        assert_eq!(s.zscore(0).data[0], -1.5f64.sqrt());
+        assert_eq!(s.zscore(0).data[1], 0.0);
+        assert_eq!(s.minmax_scale().data, vec![0.0, 0.5, 1.0]);
+        assert!(Series::from(&[2, 2]).zscore(1).data[0].is_nan());
+    }
+
+    #[test]
+    fn fold_threads_an_accumulator() {
+        let s = Series::from(&[1, 2, 3]);
+        assert_eq!(s.fold(0, |acc, x| acc + x * x), 14);
+        assert_eq!(s.fold(String::new(), |acc, x| acc + &x.to_string()), "123");
+        assert_eq!(Series::<i32>::from(&[]).fold(7, |acc, x| acc + x), 7);
+    }
+
+    #[test]
+    fn windows_and_chunks_borrow_the_values() {
+        let s = Series::from(&[1, 2, 3, 4, 5]);
+        assert_eq!(
+            s.windows(2)
+                .map(|w| w.iter().sum::<i32>())
+                .collect::<Vec<_>>(),
+            vec![3, 5, 7, 9]
+        );
+        assert_eq!(s.windows(6).count(), 0);
+        assert_eq!(
+            s.chunks(2).collect::<Vec<_>>(),
+            vec![&[1, 2][..], &[3, 4], &[5]]
+        );
+    }
+
+    #[test]
+    fn get_loc_requires_a_unique_label() {
+        let s = Series::from_label(&[1, 2, 3, 4], &["a", "b", "c", "b"]);
+        assert_eq!(s.get_loc(&"c").unwrap(), 2);
+        assert!(s.get_loc(&"b").is_err());
+        assert_eq!(
+            s.get_loc(&"z").unwrap_err().to_string(),
+            "label not found : z"
+        );
+        assert_eq!(
+            s.get_indexer(&["b", "z", "a"]),
+            vec![Some(1), None, Some(0)]
+        );
+    }
+
+    #[test]
+    fn drop_removes_every_matching_label() {
+        let s = Series::from_label(&[1, 2, 3, 4], &["a", "b", "c", "b"]);
+        let dropped = s.drop(&["b", "z"]);
+        assert_eq!(dropped.as_parts(), (&[1, 3][..], &["a", "c"][..]));
+        assert!(matches!(
+            s.try_drop(&["b", "z"]),
+            Err(RudasError::LabelNotFound(_))
+        ));
+        assert_eq!(s.try_drop(&["a"]).unwrap().data, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn checked_aggregations_detect_overflow() {
+        let s = Series::from(&[i32::MAX, 1]);
+        assert_eq!(s.checked_sum(), None);
+        assert_eq!(s.sum_as::<i64>(), i32::MAX as i64 + 1);
+        assert!(s.checked_cumsum().is_none());
+        assert_eq!(Series::from(&[i64::MAX, 1]).checked_sum(), None);
+        assert_eq!(
+            Series::from(&[i64::MAX, i64::MAX]).sum_as::<i128>(),
+            2 * i64::MAX as i128
+        );
+        assert_eq!(Series::from(&[1 << 20, 1 << 20]).checked_product(), None);
+        assert_eq!(Series::from(&[f64::MAX, f64::MAX]).checked_sum(), None);
+
+        let small = Series::from(&[1, 2, 3]);
+        assert_eq!(small.checked_sum(), Some(6));
+        assert_eq!(small.checked_product(), Some(6));
+        assert_eq!(small.checked_cumsum().unwrap().data, vec![1, 3, 6]);
+    }
+
+    #[test]
+    fn kahan_sum_keeps_the_lost_precision() {
+        let s = Series::from(&[1.0, 1e100, 1.0, -1e100]);
+        assert_eq!(s.sum(), 0.0);
+        assert_eq!(s.sum_kahan(), 2.0);
+        let tenths = Series::from(&[0.1; 10]);
+        assert_ne!(tenths.sum(), 1.0);
+        assert_eq!(tenths.sum_kahan(), 1.0);
+    }
+
+    #[test]
+    fn take_gathers_positions_with_repeats() {
+        let s = Series::from_label(&[1, 2, 3], &["a", "b", "c"]);
+        let taken = s.take(&[2, 0, 2]).unwrap();
+        assert_eq!(taken.as_parts(), (&[3, 1, 3][..], &["c", "a", "c"][..]));
+        assert_eq!(
+            s.take(&[0, 5]).unwrap_err().to_string(),
+            "out of bounds : index 5 for length 3"
+        );
+    }
+
+    #[test]
+    fn builder_collects_pairs_and_checks_uniqueness() {
+        let mut builder = SeriesBuilder::with_capacity(3).push("a", 1);
+        for (label, value) in [("b", 2), ("a", 3)] {
+            builder = builder.push(label, value);
+        }
+        let s = builder.build().unwrap();
+        assert_eq!(s.as_parts(), (&[1, 2, 3][..], &["a", "b", "a"][..]));
+
+        let duplicated = SeriesBuilder::new()
+            .push("a", 1)
+            .push("a", 2)
+            .require_unique()
+            .build();
+        assert_eq!(duplicated.unwrap_err().to_string(), "duplicate entry : a");
+        assert!(SeriesBuilder::<f64, usize>::default()
+            .build()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn prod_and_agg_summary() {
+        let s = Series::from(&[1, 2, 3, 4]);
+        assert_eq!(s.prod(), 24);
+        assert_eq!(s.agg_summary(), (10, 24, 2.5));
+        let empty = Series::<i32>::from(&[]);
+        assert_eq!(empty.prod(), 1);
+        assert!(empty.agg_summary().2.is_nan());
+    }
+
+    #[test]
+    fn explode_repeats_labels_and_drops_empty_lists() {
+        let s = Series::from_label(&[vec![1, 2], vec![], vec![3]], &["a", "b", "c"]);
+        let exploded = s.explode();
+        assert_eq!(exploded.as_parts(), (&[1, 2, 3][..], &["a", "a", "c"][..]));
+    }
+
+    /// Sample covariance and Pearson correlation of one window, recomputed
+    /// from scratch.
+    fn naive_cov_corr(x: &[f64], y: &[f64]) -> (f64, f64) {
+        let n = x.len() as f64;
+        let mx = x.iter().sum::<f64>() / n;
+        let my = y.iter().sum::<f64>() / n;
+        let c: f64 = x.iter().zip(y).map(|(a, b)| (a - mx) * (b - my)).sum();
+        let vx: f64 = x.iter().map(|a| (a - mx).powi(2)).sum();
+        let vy: f64 = y.iter().map(|b| (b - my).powi(2)).sum();
+        (c / (n - 1.0), c / (vx * vy).sqrt())
+    }
+
+    #[test]
+    fn rolling_corr_and_cov_match_naive_windows() {
+        let mut state = 7u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 11) as f64 / (1u64 << 53) as f64) * 100.0 - 30.0
+        };
+        let x: Vec<f64> = (0..2000).map(|_| next()).collect();
+        let y: Vec<f64> = x.iter().map(|v| v * 0.3 + next()).collect();
+        let (a, b) = (Series::from(&x), Series::from(&y));
+        for window in [2, 5, 37] {
+            let corr = a.rolling_corr(&b, window).unwrap();
+            let cov = a.rolling_cov(&b, window).unwrap();
+            for i in 0..x.len() {
+                if i + 1 < window {
+                    assert!(corr.data[i].is_none() && cov.data[i].is_none());
+                    continue;
+                }
+                let range = i + 1 - window..=i;
+                let (c, r) = naive_cov_corr(&x[range.clone()], &y[range]);
+                assert!((cov.data[i].unwrap() - c).abs() < 1e-7 * c.abs().max(1.0));
+                assert!((corr.data[i].unwrap() - r).abs() < 1e-7);
+            }
+        }
+    }
+
+    #[test]
+    fn rolling_corr_is_nan_for_constant_windows() {
+        let f = Series::from(&[1.0, 5.0, 3.0, 3.0, 3.0, 3.0, 0.1, 0.1, 0.1]);
+        let g = Series::from(&[2.0, 1.0, 4.0, 6.0, 7.0, 1.0, 2.0, 3.0, 4.0]);
+        let corr = f.rolling_corr(&g, 3).unwrap();
+        assert!(corr.data[1].is_none());
+        assert!(!corr.data[2].unwrap().is_nan());
+        for i in [4, 5, 8] {
+            assert!(corr.data[i].unwrap().is_nan());
+        }
+        assert!(f
+            .rolling_cov(&g, 1)
+            .unwrap()
+            .data
+            .iter()
+            .all(|v| v.unwrap().is_nan()));
+        assert!(f.rolling_cov(&g, 0).is_err());
+        assert!(f.rolling_corr(&Series::from(&[1.0]), 2).is_err());
+    }
+
+    #[test]
+    fn outlier_mask_flags_a_clear_outlier() {
+        let s = Series::from(&[1.0, 2.0, 3.0, 4.0, 5.0, 100.0]);
+        assert_eq!(s.iqr(), 4.75 - 2.25);
+        assert_eq!(
+            s.outlier_mask(1.5).data,
+            vec![false, false, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn histogram_counts_equal_width_bins() {
+        let s = Series::from(&[0.0, 0.5, 1.0, 1.5, 2.0, f64::NAN]);
+        let hist = s.histogram(2).unwrap();
+        assert_eq!(hist.data, vec![2, 3]);
+        assert_eq!(
+            hist.label.iter().map(|i| i.to_string()).collect::<Vec<_>>(),
+            vec!["[0, 1)", "[1, 2]"]
+        );
+        let constant = Series::from(&[3, 3, 3]).histogram(3).unwrap();
+        assert_eq!(constant.data.iter().sum::<usize>(), 3);
+        assert_eq!(constant.data.iter().filter(|&&n| n > 0).count(), 1);
+        assert!(Series::<f64>::from(&[]).histogram(4).unwrap().is_empty());
+        assert!(s.histogram(0).is_err());
+    }
+
+    #[test]
+    fn map_with_label_sees_each_label() {
+        let s = Series::from(&[1.0, 2.0, 3.0]);
+        let scaled = s.map_with_label(|l, v| v * *l as f64);
+        assert_eq!(scaled.as_parts(), (&[0.0, 2.0, 6.0][..], &[0, 1, 2][..]));
+    }
+
+    #[test]
+    fn view_borrows_a_range() {
+        let s = Series::from_label(&[1, 2, 3, 4], &["a", "b", "c", "d"]);
+        let view = s.view(1..3).unwrap();
+        assert_eq!(view.len(), 2);
+        assert_eq!(view.get(0), Some((&"b", &2)));
+        assert_eq!(view.get(2), None);
+        assert_eq!(view.iter().map(|(_, x)| *x).sum::<i32>(), 5);
+        assert_eq!(view.to_series().label, vec!["b", "c"]);
+        assert!(s.view(4..4).unwrap().is_empty());
+        assert!(s.view(2..5).is_err());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = s.view(3..1);
+        assert!(reversed.is_err());
+    }
+
+    #[test]
+    fn weighted_mean_of_unequal_weights() {
+        let s = Series::from(&[10.0, 20.0, 30.0]);
+        let w = Series::from(&[1.0, 2.0, 7.0]);
+        assert_eq!(s.weighted_mean(&w).unwrap(), 26.0);
+        assert_eq!(
+            Series::from(&[1i64, 3])
+                .weighted_mean(&Series::from(&[3i64, 1]))
+                .unwrap(),
+            1.5
+        );
+        assert!(s.weighted_mean(&Series::from(&[0.0, 0.0, 0.0])).is_err());
+        assert!(s.weighted_mean(&Series::from(&[1.0])).is_err());
+        assert!(s
+            .weighted_mean(&Series::from_label(&[1.0, 1.0, 1.0], &[0, 1, 3]))
+            .is_err());
+    }
+
+    #[test]
+    fn rolling_corr_requires_aligned_series() {
+        let a = Series::from_label(&[1.0, 2.0, 3.0], &["x", "y", "z"]);
+        let shifted = Series::from_label(&[1.0, 2.0, 4.0], &["y", "z", "w"]);
+        assert!(a.rolling_corr(&shifted, 2).is_err());
+        let aligned = Series::from_label(&[2.0, 4.0, 5.0], &["x", "y", "z"]);
+        let corr = a.rolling_corr(&aligned, 2).unwrap();
+        assert_eq!(corr.data[0], None);
+        assert!(corr.data[1..]
+            .iter()
+            .all(|r| (r.unwrap() - 1.0).abs() < 1e-12));
+        assert_eq!(corr.label, a.label);
+        let ints = Series::from(&[1i64, 2, 3]);
+        assert!(ints
+            .rolling_corr(&Series::from(&[1.0, 2.0, 3.5]), 2)
+            .unwrap()
+            .data[2]
+            .is_some());
+    }
+
+    #[test]
+    fn zip_apply_checks_lengths_and_optionally_labels() {
+        let a = Series::from_label(&[1, 2, 3], &["x", "y", "z"]);
+        let b = Series::from_label(&[1.5, 2.5, 3.5], &["p", "q", "r"]);
+        let c = Series::from_label(&[10, 20, 30], &["x", "y", "z"]);
+        let product = a.zip_apply(&b, false, |x, y| *x as f64 * y).unwrap();
+        assert_eq!(
+            product.as_parts(),
+            (&[1.5, 5.0, 10.5][..], &["x", "y", "z"][..])
+        );
+        assert!(a.zip_apply(&b, true, |x, y| *x as f64 * y).is_err());
+        assert!(matches!(
+            a.zip_apply(&Series::from_label(&[1], &["x"]), false, |x, y| x + y),
+            Err(RudasError::LengthMismatch {
+                expected: 3,
+                found: 1
+            })
+        ));
+        assert_eq!(
+            a.zip_apply3(&c, &c, true, |x, y, z| x * y + z)
+                .unwrap()
+                .data,
+            vec![20, 60, 120]
+        );
+        assert!(a.zip_apply3(&c, &b, true, |_, _, _| 0).is_err());
+    }
+
+    #[test]
+    fn relabel_with_changes_the_label_type() {
+        let s = Series::from(&[1, 2]).with_name("n").with_index_name("i");
+        let relabeled = s.relabel_with(|l| format!("row{}", l));
+        assert_eq!(relabeled.label, vec!["row0", "row1"]);
+        assert_eq!(relabeled.data, vec![1, 2]);
+        assert_eq!(relabeled.name.as_deref(), Some("n"));
+        assert_eq!(relabeled.index_name.as_deref(), Some("i"));
+    }
+
+    #[test]
+    fn label_index_finds_first_occurrences() {
+        let s = Series::from_label(&[1, 2, 3], &["a", "b", "a"]);
+        assert_eq!(s.loc(&"a"), Some(&1));
+        assert_eq!(s.loc(&"z"), None);
+        let index = s.build_index();
+        assert_eq!(index.loc(&"b"), Some(&2));
+        assert_eq!(index.get_loc(&"a"), Some(0));
+        assert!(!index.contains(&"z"));
+    }
+
+    #[test]
+    fn winsorize_and_mask_select() {
+        let s = Series::from(&[1.0, 2.0, 3.0, 4.0, 5.0, 100.0]);
+        let kept = s.mask_select(&!s.outlier_mask(1.5)).unwrap();
+        assert_eq!(kept.data, vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert!(s.mask_select(&Series::from(&[true])).is_err());
+
+        let clipped = s.winsorize(0.0, 0.8).unwrap();
+        assert_eq!((clipped.data[0], clipped.data[5]), (1.0, 5.0));
+        assert!(s.winsorize(0.9, 0.1).is_err());
+        let constant = Series::from(&[2, 2, 2]);
+        assert!(constant
+            .winsorize(0.1, 0.9)
+            .unwrap()
+            .data
+            .iter()
+            .all(|&x| x == 2.0));
+        assert!(constant.zscore(1).data.iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn resize_truncates_and_extends() {
+        let s = Series::from(&[1, 2, 3]);
+        let truncated = s.resize(2, 0, |i| i);
+        assert_eq!(truncated.as_parts(), (&[1, 2][..], &[0, 1][..]));
+        let extended = s.resize(5, 0, |i| i * 10);
+        assert_eq!(extended.data, vec![1, 2, 3, 0, 0]);
+        assert_eq!(extended.label, vec![0, 1, 2, 30, 40]);
+        assert_eq!(s.resize(3, 0, |i| i).as_parts(), s.as_parts());
+    }
+
+    #[test]
+    fn cum_nunique_plateaus_on_repeats() {
+        let s = Series::from(&["a", "b", "a", "a", "c", "b"]);
+        assert_eq!(s.cum_nunique().data, vec![1, 2, 2, 2, 3, 3]);
+        assert_eq!(Series::from(&[7; 4]).cum_nunique().data, vec![1; 4]);
+    }
+
+    #[test]
+    fn between_includes_bounds_only_when_inclusive() {
+        let s = Series::from(&[1, 2, 3, 4, 5]);
+        assert_eq!(
+            s.between(2, 4, true).data,
+            vec![false, true, true, true, false]
+        );
+        assert_eq!(
+            s.between(2, 4, false).data,
+            vec![false, false, true, false, false]
+        );
+        assert_eq!(
+            s.between(3, 3, true).data,
+            vec![false, false, true, false, false]
+        );
+        assert!(s.between(3, 3, false).data.iter().all(|b| !b));
+    }
+
+    #[test]
+    fn cummin_and_cummax_skip_leading_and_inner_nan() {
+        let s = Series::from(&[3, 1, 4, 1, 5]);
+        assert_eq!(s.cummax().data, vec![3, 3, 4, 4, 5]);
+        assert_eq!(s.cummin().data, vec![3, 1, 1, 1, 1]);
+        let f = Series::from(&[f64::NAN, 2.0, f64::NAN, 1.0, 3.0]);
+        let high = f.cummax();
+        assert!(high.data[0].is_nan());
+        assert_eq!(&high.data[1..], &[2.0, 2.0, 2.0, 3.0]);
+        let low = f.cummin();
+        assert!(low.data[0].is_nan());
+        assert_eq!(&low.data[1..], &[2.0, 2.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn merge_asof_takes_the_last_label_not_after() {
+        let a = Series::from_label(&[1, 2, 3, 4], &[1, 5, 10, 12]);
+        let b = Series::from_label(&[100, 200, 300], &[2, 5, 11]);
+        let m = a.merge_asof(&b).unwrap();
+        assert_eq!(
+            m.data,
+            vec![(1, None), (2, Some(200)), (3, Some(200)), (4, Some(300))]
+        );
+        assert_eq!(m.label, a.label);
+        let unsorted = Series::from_label(&[1, 2], &[2, 1]);
+        assert!(unsorted.merge_asof(&b).is_err());
+        assert!(b.merge_asof(&unsorted).is_err());
+    }
+
+    #[test]
+    fn parse_column_keeps_each_result() {
+        let s = Series::from(&["1".to_string(), "x".to_string(), " 3".to_string()]);
+        let parsed = s.parse_column::<i64>();
+        assert_eq!(parsed.data[0], Ok(1));
+        assert!(parsed.data[1].is_err());
+        assert!(parsed.data[2].is_err());
+        assert_eq!(parsed.label, s.label);
+        assert_eq!(
+            s.parse_column_lossy::<i64>().data,
+            vec![Some(1), None, None]
+        );
+        assert_eq!(
+            s.parse_column_lossy::<f64>().data,
+            vec![Some(1.0), None, None]
+        );
+    }
+
+    #[test]
+    fn default_series_extends_in_lockstep() {
+        let mut s: Series<i32> = Series::default();
+        assert!(s.is_empty());
+        assert_eq!(s.name, None);
+        s.extend(vec![(0, 1), (1, 2)]);
+        s.extend(std::iter::once((7, 3)));
+        assert_eq!(s.data, vec![1, 2, 3]);
+        assert_eq!(s.label, vec![0, 1, 7]);
+    }
+
+    #[test]
+    fn autocorr_peaks_at_the_period() {
+        let v: Vec<f64> = (0..40).map(|i| [1.0, 3.0, 2.0, 0.0][i % 4]).collect();
+        let s = Series::from(&v);
+        assert!((s.autocorr(4) - 1.0).abs() < 1e-12);
+        assert!((s.autocorr(8) - 1.0).abs() < 1e-12);
+        assert!(s.autocorr(2) < 0.0);
+        assert!(s.autocorr(1) < s.autocorr(4));
+        assert!(s.autocorr(39).is_nan());
+        assert!(s.autocorr(100).is_nan());
+        assert!(Series::from(&[1.0, 1.0, 1.0]).autocorr(1).is_nan());
+    }
+
+    #[test]
+    fn checked_arithmetic_marks_overflow() {
+        let a = Series::from(&[i8::MAX, 1, 100]);
+        let b = Series::from(&[1i8, 2, 2]);
+        assert_eq!(
+            a.checked_add(&b).unwrap().data,
+            vec![None, Some(3), Some(102)]
+        );
+        assert_eq!(
+            a.checked_mul(&b).unwrap().data,
+            vec![Some(127), Some(2), None]
+        );
+        let c = Series::from_label(&[1i8, 2, 2], &[5, 6, 7]);
+        assert!(a.checked_add(&c).is_err());
+        assert!(a.checked_mul(&c).is_err());
+    }
+
+    #[test]
+    fn values_eq_ignores_labels() {
+        let a = Series::from_label(&[1, 2], &[0, 1]);
+        let b = Series::from_label(&[1, 2], &[7, 8]);
+        assert!(a.values_eq(&b));
+        assert_ne!(a.label, b.label);
+        assert!(!a.values_eq(&Series::from_label(&[1], &[0])));
+        assert!(!a.values_eq(&Series::from_label(&[2, 1], &[0, 1])));
+    }
+
+    #[test]
+    fn rolling_median_resists_outliers() {
+        let s = Series::from(&[1, 100, 3, 4, 2]);
+        assert_eq!(
+            s.rolling_median(3).unwrap().data,
+            vec![None, None, Some(3.0), Some(4.0), Some(3.0)]
+        );
+        assert_eq!(s.rolling_median(2).unwrap().data[1], Some(50.5));
+        assert_eq!(s.rolling_median(9).unwrap().data, vec![None; 5]);
+        assert!(s.rolling_median(0).is_err());
+    }
+
+    #[test]
+    fn to_int_lossless_needs_whole_values_in_range() {
+        let s = Series::from_label(&[1.0, -2.0, 0.0], &[4, 5, 6]);
+        let ints = s.to_int_lossless().unwrap();
+        assert_eq!(ints.data, vec![1, -2, 0]);
+        assert_eq!(ints.label, s.label);
+        assert!(Series::from(&[1.0, 2.5]).to_int_lossless().is_none());
+        assert!(Series::from(&[f64::NAN]).to_int_lossless().is_none());
+        assert!(Series::from(&[f64::INFINITY]).to_int_lossless().is_none());
+        assert!(Series::from(&[9.3e18]).to_int_lossless().is_none());
+    }
+
+    #[test]
+    fn rolling_map_sees_each_full_window() {
+        let s = Series::from(&[1, 5, 2]);
+        let r = s
+            .rolling_map(2, |w| (w[0], w[1], format!("{:?}", w)))
+            .unwrap();
+        assert_eq!(
+            r.data,
+            vec![
+                None,
+                Some((1, 5, "[1, 5]".to_string())),
+                Some((5, 2, "[5, 2]".to_string()))
+            ]
+        );
+        assert_eq!(
+            s.rolling_map(5, |w| w.len()).unwrap().data,
+            vec![None, None, None]
+        );
+        assert!(s.rolling_map(0, |w| w.len()).is_err());
+    }
+
+    #[test]
+    fn debug_previews_long_series() {
+        let s = Series::from(&(0..1000).collect::<Vec<i32>>());
+        assert_eq!(
+            format!("{:?}", s),
+            "Series { len: 1000, data: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, ...], \
+             label: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, ...] }"
+        );
+        let exact = Series::from(&(0..DEBUG_PREVIEW).collect::<Vec<_>>());
+        assert!(!format!("{:?}", exact).contains("..."));
+        assert_eq!(
+            format!("{:?}", Series::from(&[1, 2])),
+            "Series { len: 2, data: [1, 2], label: [0, 1] }"
+        );
+    }
+
+    #[test]
+    fn zips_reject_misaligned_series() {
+        let a = Series::from(&[1, 2]);
+        let b = Series::from(&["x", "y"]);
+        let c = Series::from(&[0.5, 1.5]);
+        assert_eq!(zip2(&a, &b).unwrap().data, vec![(1, "x"), (2, "y")]);
+        assert_eq!(
+            zip3(&a, &b, &c).unwrap().data,
+            vec![(1, "x", 0.5), (2, "y", 1.5)]
+        );
+        assert!(zip2(&a, &Series::from_label(&[1, 2], &[1, 0])).is_err());
+        assert!(zip3(&a, &b, &Series::from(&[1.0])).is_err());
+    }
+
+    #[test]
+    fn apply_inplace_keeps_labels_and_names() {
+        let mut s = Series::from_label(&[1, 2, 3], &["a", "b", "c"]).with_name("n");
+        s.apply_inplace(|x| *x *= 10);
+        assert_eq!(s.data, vec![10, 20, 30]);
+        assert_eq!(s.label, vec!["a", "b", "c"]);
+        assert_eq!(s.name.as_deref(), Some("n"));
+    }
+
+    #[test]
+    fn try_from_vectors_checks_lengths() {
+        let s = Series::try_from((vec![1, 2], vec!["a", "b"])).unwrap();
+        assert_eq!(s.data, vec![1, 2]);
+        assert_eq!(s.label, vec!["a", "b"]);
+        assert!(matches!(
+            Series::try_from((vec![1, 2], vec!["a"])),
+            Err(RudasError::LengthMismatch {
+                expected: 1,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn rolling_weighted_mean_applies_the_kernel() {
+        let s = Series::from(&[1, 2, 3, 4]);
+        assert_eq!(
+            s.rolling_weighted_mean(&[1.0, 2.0, 1.0]).unwrap().data,
+            vec![None, None, Some(2.0), Some(3.0)]
+        );
+        assert_eq!(
+            s.rolling_weighted_mean(&[0.0, 1.0]).unwrap().data,
+            vec![None, Some(2.0), Some(3.0), Some(4.0)]
+        );
+        assert_eq!(
+            s.rolling_weighted_mean(&[1.0, 1.0]).unwrap().data,
+            s.rolling_mean(2).unwrap().data
+        );
+        assert!(s.rolling_weighted_mean(&[]).is_err());
+        assert!(s.rolling_weighted_mean(&[0.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn dtype_names_the_value_and_label_types() {
+        let s = Series::from_label(&[1.0f64], &["a".to_string()]);
+        assert_eq!(s.dtype(), type_name::<f64>());
+        assert_eq!(s.label_dtype(), type_name::<String>());
+        assert!(s.label_dtype().contains("String"));
+        let ints = Series::from(&[1i32]);
+        assert_eq!(ints.dtype(), type_name::<i32>());
+        assert_eq!(ints.label_dtype(), type_name::<usize>());
+    }
+
+    #[test]
+    fn debug_shows_names_only_when_set() {
+        let s = Series::from(&[1, 2])
+            .with_name("price")
+            .with_index_name("day");
+        assert_eq!(
+            format!("{:?}", s),
+            "Series { name: \"price\", index_name: \"day\", len: 2, data: [1, 2], label: [0, 1] }"
+        );
+        let named = Series::from(&[1]).with_name("price");
+        assert!(!format!("{:?}", named).contains("index_name"));
+    }
+
+    #[test]
+    fn cut_intervals_labels_values_by_their_bin() {
+        let s = Series::from(&[0.0, 0.5, 1.0, 1.5, 3.0, f64::NAN]);
+        let binned = s.cut_intervals(&[0.0, 1.0, 2.0]).unwrap();
+        let lo = Interval::new(0.0, 1.0, Closed::Right);
+        let hi = Interval::new(1.0, 2.0, Closed::Right);
+        assert_eq!(
+            binned.data,
+            vec![None, Some(lo), Some(lo), Some(hi), None, None]
+        );
+        assert_eq!(binned.data[1].unwrap().to_string(), "(0, 1]");
+        assert!(s.cut_intervals(&[1.0]).is_err());
+        assert!(s.cut_intervals(&[1.0, 1.0]).is_err());
+    }
+
+    #[test]
+    fn qcut_with_edges_returns_the_bin_ranges() {
+        let s = Series::from(&[1.0, 2.0, 3.0, f64::NAN, 4.0, 5.0]);
+        let (bins, edges) = s.qcut_with_edges(2).unwrap();
+        assert_eq!(bins.data, s.qcut(2).unwrap().data);
+        assert_eq!(
+            edges,
+            vec![
+                Interval::new(1.0, 3.0, Closed::Both),
+                Interval::new(3.0, 5.0, Closed::Right)
+            ]
+        );
+        for (bin, x) in bins.data.iter().zip(&s.data) {
+            if let Some(i) = bin {
+                assert!(edges[*i].contains(x));
+            }
         }
-        println!("type : {}", type_name::<T>());
+        let (_, one) = s.qcut_with_edges(1).unwrap();
+        assert_eq!(one, vec![Interval::new(1.0, 5.0, Closed::Both)]);
+        let (_, none) = Series::from(&[f64::NAN]).qcut_with_edges(3).unwrap();
+        assert!(none.is_empty());
     }
 }
@@ -0,0 +1,120 @@
+use std::{error::Error, fmt::Display, io};
+
+#[derive(Debug)]
+pub enum RudasError {
+    Io(io::Error),
+    #[cfg(feature = "arrow")]
+    Arrow(::arrow::error::ArrowError),
+    #[cfg(feature = "parquet")]
+    Parquet(::parquet::errors::ParquetError),
+    Parse {
+        line: usize,
+        message: String,
+    },
+    InvalidArgument(String),
+    InvalidFormat(String),
+    ColumnNotFound(String),
+    LabelNotFound(String),
+    DuplicateColumn(String),
+    DuplicateEntry(String),
+    MissingValue {
+        column: String,
+        label: String,
+    },
+    LengthMismatch {
+        expected: usize,
+        found: usize,
+    },
+    OutOfBounds {
+        index: usize,
+        len: usize,
+    },
+    TypeMismatch {
+        column: String,
+        expected: &'static str,
+    },
+    Cast {
+        column: String,
+        label: String,
+        message: String,
+    },
+}
+
+impl Display for RudasError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RudasError::Io(e) => write!(f, "io error : {}", e),
+            #[cfg(feature = "arrow")]
+            RudasError::Arrow(e) => write!(f, "arrow error : {}", e),
+            #[cfg(feature = "parquet")]
+            RudasError::Parquet(e) => write!(f, "parquet error : {}", e),
+            RudasError::Parse { line, message } => {
+                write!(f, "parse error at line {} : {}", line, message)
+            }
+            RudasError::InvalidArgument(message) => write!(f, "invalid argument : {}", message),
+            RudasError::InvalidFormat(message) => write!(f, "invalid format : {}", message),
+            RudasError::ColumnNotFound(name) => write!(f, "column not found : {}", name),
+            RudasError::LabelNotFound(label) => write!(f, "label not found : {}", label),
+            RudasError::DuplicateColumn(name) => write!(f, "duplicate column : {}", name),
+            RudasError::DuplicateEntry(key) => write!(f, "duplicate entry : {}", key),
+            RudasError::MissingValue { column, label } => {
+                write!(f, "missing value : column {} at label {}", column, label)
+            }
+            RudasError::LengthMismatch { expected, found } => {
+                write!(
+                    f,
+                    "length mismatch : expected {}, found {}",
+                    expected, found
+                )
+            }
+            RudasError::OutOfBounds { index, len } => {
+                write!(f, "out of bounds : index {} for length {}", index, len)
+            }
+            RudasError::TypeMismatch { column, expected } => {
+                write!(f, "type mismatch : column {} is not {}", column, expected)
+            }
+            RudasError::Cast {
+                column,
+                label,
+                message,
+            } => write!(
+                f,
+                "cast error : column {} at label {} : {}",
+                column, label, message
+            ),
+        }
+    }
+}
+
+impl Error for RudasError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RudasError::Io(e) => Some(e),
+            #[cfg(feature = "arrow")]
+            RudasError::Arrow(e) => Some(e),
+            #[cfg(feature = "parquet")]
+            RudasError::Parquet(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RudasError {
+    fn from(e: io::Error) -> RudasError {
+        RudasError::Io(e)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<::parquet::errors::ParquetError> for RudasError {
+    fn from(e: ::parquet::errors::ParquetError) -> RudasError {
+        RudasError::Parquet(e)
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl From<::arrow::error::ArrowError> for RudasError {
+    fn from(e: ::arrow::error::ArrowError) -> RudasError {
+        RudasError::Arrow(e)
+    }
+}
@@ -0,0 +1,1158 @@
+use std::{cmp::Ordering, collections::HashMap, fmt::Display};
+
+use crate::{csv::parse_bool, data::Series, error::RudasError, record::Field};
+
+/// Name of the column that carries the row labels when a frame is exchanged
+/// with columnar formats such as Parquet and Arrow.
+pub const LABEL_COLUMN: &str = "__label__";
+
+/// Fails when a column is named `LABEL_COLUMN`, which would collide with the
+/// row labels once written.
+#[cfg(any(feature = "arrow", feature = "parquet"))]
+pub(crate) fn check_label_column(df: &DataFrame) -> Result<(), RudasError> {
+    if df.columns.iter().any(|(name, _)| name == LABEL_COLUMN) {
+        return Err(RudasError::InvalidArgument(format!(
+            "column name {} is reserved for the row labels",
+            LABEL_COLUMN
+        )));
+    }
+    Ok(())
+}
+
+/// Row labels read back from a `LABEL_COLUMN` column, which must hold
+/// strings and no missing value.
+#[cfg(any(feature = "arrow", feature = "parquet"))]
+pub(crate) fn labels_from_column(column: Column) -> Result<Vec<String>, RudasError> {
+    match column {
+        Column::Str(v) => v
+            .into_iter()
+            .enumerate()
+            .map(|(i, label)| {
+                label.ok_or_else(|| RudasError::MissingValue {
+                    column: LABEL_COLUMN.to_string(),
+                    label: i.to_string(),
+                })
+            })
+            .collect(),
+        _ => Err(RudasError::TypeMismatch {
+            column: LABEL_COLUMN.to_string(),
+            expected: "a string column",
+        }),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    I64(i64),
+    F64(f64),
+    Bool(bool),
+    Str(String),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::I64(v) => write!(f, "{}", v),
+            Value::F64(v) => write!(f, "{}", v),
+            Value::Bool(v) => write!(f, "{}", v),
+            Value::Str(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DType {
+    I64,
+    F64,
+    Bool,
+    Str,
+}
+
+impl DType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DType::I64 => "i64",
+            DType::F64 => "f64",
+            DType::Bool => "bool",
+            DType::Str => "str",
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, DType::I64 | DType::F64)
+    }
+}
+
+impl Display for DType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A typed column of a `DataFrame`. `None` cells are missing values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    I64(Vec<Option<i64>>),
+    F64(Vec<Option<f64>>),
+    Bool(Vec<Option<bool>>),
+    Str(Vec<Option<String>>),
+}
+
+macro_rules! each_column {
+    ($column:expr, $v:ident => $body:expr) => {
+        match $column {
+            Column::I64($v) => $body,
+            Column::F64($v) => $body,
+            Column::Bool($v) => $body,
+            Column::Str($v) => $body,
+        }
+    };
+}
+
+macro_rules! map_column {
+    ($column:expr, $v:ident => $body:expr) => {
+        match $column {
+            Column::I64($v) => Column::I64($body),
+            Column::F64($v) => Column::F64($body),
+            Column::Bool($v) => Column::Bool($body),
+            Column::Str($v) => Column::Str($body),
+        }
+    };
+}
+
+macro_rules! impl_column_from {
+    ($t:ty, $variant:ident) => {
+        impl From<Vec<$t>> for Column {
+            fn from(v: Vec<$t>) -> Column {
+                Column::$variant(v.into_iter().map(Some).collect())
+            }
+        }
+
+        impl From<Vec<Option<$t>>> for Column {
+            fn from(v: Vec<Option<$t>>) -> Column {
+                Column::$variant(v)
+            }
+        }
+    };
+}
+
+impl_column_from!(i64, I64);
+impl_column_from!(f64, F64);
+impl_column_from!(bool, Bool);
+impl_column_from!(String, Str);
+
+impl From<Vec<&str>> for Column {
+    fn from(v: Vec<&str>) -> Column {
+        Column::Str(v.into_iter().map(|s| Some(s.to_string())).collect())
+    }
+}
+
+impl Column {
+    pub fn len(&self) -> usize {
+        each_column!(self, v => v.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn dtype(&self) -> DType {
+        match self {
+            Column::I64(_) => DType::I64,
+            Column::F64(_) => DType::F64,
+            Column::Bool(_) => DType::Bool,
+            Column::Str(_) => DType::Str,
+        }
+    }
+
+    pub fn is_null(&self, i: usize) -> bool {
+        each_column!(self, v => v[i].is_none())
+    }
+
+    pub fn get(&self, i: usize) -> Option<Value> {
+        match self {
+            Column::I64(v) => v[i].map(Value::I64),
+            Column::F64(v) => v[i].map(Value::F64),
+            Column::Bool(v) => v[i].map(Value::Bool),
+            Column::Str(v) => v[i].clone().map(Value::Str),
+        }
+    }
+
+    /// Reads a numeric cell as `f64`. Missing cells and non-numeric columns
+    /// yield `None`.
+    pub fn f64_at(&self, i: usize) -> Option<f64> {
+        match self {
+            Column::I64(v) => v[i].map(|x| x as f64),
+            Column::F64(v) => v[i],
+            _ => None,
+        }
+    }
+
+    /// Compares two cells of the column. Missing values and `NAN` are ordered
+    /// last regardless of `ascending`.
+    pub(crate) fn compare(&self, a: usize, b: usize, ascending: bool) -> Ordering {
+        match self {
+            Column::I64(v) => compare_cells(v[a].as_ref(), v[b].as_ref(), ascending),
+            Column::F64(v) => compare_cells(
+                v[a].as_ref().filter(|x| !x.is_nan()),
+                v[b].as_ref().filter(|x| !x.is_nan()),
+                ascending,
+            ),
+            Column::Bool(v) => compare_cells(v[a].as_ref(), v[b].as_ref(), ascending),
+            Column::Str(v) => compare_cells(v[a].as_ref(), v[b].as_ref(), ascending),
+        }
+    }
+
+    /// Formats a cell as text, or `None` when it is missing. Floats use
+    /// `precision` digits after the decimal point when given and otherwise
+    /// always show a fractional part or exponent.
+    pub fn format_cell(&self, i: usize, precision: Option<usize>) -> Option<String> {
+        match (self, precision) {
+            (Column::F64(v), Some(p)) => v[i].map(|x| format!("{:.*}", p, x)),
+            (Column::F64(v), None) => v[i].map(|x| format!("{:?}", x)),
+            _ => self.get(i).map(|v| v.to_string()),
+        }
+    }
+
+    /// Converts the column to `dtype`, failing at the first cell that has no
+    /// exact counterpart with its position and the reason. Floats only
+    /// convert to integers when they are whole and in range, numbers convert
+    /// to `bool` by comparing with zero and text is parsed.
+    pub(crate) fn cast(&self, dtype: DType) -> Result<Column, (usize, String)> {
+        fn cells<T>(
+            column: &Column,
+            f: impl Fn(Value) -> Option<T>,
+            dtype: DType,
+        ) -> Result<Vec<Option<T>>, (usize, String)> {
+            (0..column.len())
+                .map(|i| match column.get(i) {
+                    Some(value) => {
+                        let text = value.to_string();
+                        f(value)
+                            .map(Some)
+                            .ok_or_else(|| (i, format!("cannot cast '{}' to {}", text, dtype)))
+                    }
+                    None => Ok(None),
+                })
+                .collect()
+        }
+
+        Ok(match dtype {
+            DType::I64 => Column::I64(cells(
+                self,
+                |value| match value {
+                    Value::I64(x) => Some(x),
+                    Value::F64(x) => {
+                        (x.fract() == 0.0 && x >= i64::MIN as f64 && x < i64::MAX as f64)
+                            .then_some(x as i64)
+                    }
+                    Value::Bool(x) => Some(x as i64),
+                    Value::Str(x) => x.trim().parse().ok(),
+                },
+                dtype,
+            )?),
+            DType::F64 => Column::F64(cells(
+                self,
+                |value| match value {
+                    Value::I64(x) => Some(x as f64),
+                    Value::F64(x) => Some(x),
+                    Value::Bool(x) => Some(x as i64 as f64),
+                    Value::Str(x) => x.trim().parse().ok(),
+                },
+                dtype,
+            )?),
+            DType::Bool => Column::Bool(cells(
+                self,
+                |value| match value {
+                    Value::I64(x) => Some(x != 0),
+                    Value::F64(x) => (!x.is_nan()).then_some(x != 0.0),
+                    Value::Bool(x) => Some(x),
+                    Value::Str(x) => parse_bool(x.trim()),
+                },
+                dtype,
+            )?),
+            DType::Str => Column::Str((0..self.len()).map(|i| self.format_cell(i, None)).collect()),
+        })
+    }
+
+    #[allow(clippy::clone_on_copy)]
+    pub(crate) fn take(&self, positions: &[usize]) -> Column {
+        map_column!(self, v => positions.iter().map(|&p| v[p].clone()).collect())
+    }
+
+    #[allow(clippy::clone_on_copy)]
+    pub(crate) fn take_or_null(&self, positions: &[Option<usize>]) -> Column {
+        map_column!(self, v => positions.iter().map(|p| p.and_then(|p| v[p].clone())).collect())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Mean,
+    Sum,
+    Count,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DataFrame {
+    label: Vec<String>,
+    columns: Vec<(String, Column)>,
+}
+
+impl DataFrame {
+    pub fn new() -> DataFrame {
+        DataFrame::default()
+    }
+
+    pub fn from_columns(columns: Vec<(&str, Column)>) -> Result<DataFrame, RudasError> {
+        let mut df = DataFrame::new();
+        for (name, column) in columns {
+            df.add_column(name, column)?;
+        }
+        Ok(df)
+    }
+
+    /// Appends a column. The first column added to an empty frame sets the
+    /// default row labels `0..n`.
+    pub fn add_column(&mut self, name: &str, column: Column) -> Result<(), RudasError> {
+        if self.columns.iter().any(|(n, _)| n == name) {
+            return Err(RudasError::DuplicateColumn(name.to_string()));
+        }
+        if self.columns.is_empty() && self.label.is_empty() {
+            self.label = (0..column.len()).map(|i| i.to_string()).collect();
+        } else if column.len() != self.len() {
+            return Err(RudasError::LengthMismatch {
+                expected: self.len(),
+                found: column.len(),
+            });
+        }
+        self.columns.push((name.to_string(), column));
+        Ok(())
+    }
+
+    pub fn set_label(&mut self, label: Vec<String>) -> Result<(), RudasError> {
+        if !self.columns.is_empty() && label.len() != self.len() {
+            return Err(RudasError::LengthMismatch {
+                expected: self.len(),
+                found: label.len(),
+            });
+        }
+        self.label = label;
+        Ok(())
+    }
+
+    pub fn label(&self) -> &[String] {
+        &self.label
+    }
+
+    pub fn len(&self) -> usize {
+        self.label.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns.iter().map(|(n, _)| n.as_str()).collect()
+    }
+
+    pub fn column(&self, name: &str) -> Result<&Column, RudasError> {
+        self.columns
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, c)| c)
+            .ok_or_else(|| RudasError::ColumnNotFound(name.to_string()))
+    }
+
+    pub fn columns(&self) -> &[(String, Column)] {
+        &self.columns
+    }
+
+    /// Copies a column into a `Series` labeled by the row labels. `T` is the
+    /// cell type, e.g. `Option<f64>` to keep missing values or `f64` to
+    /// require that there are none.
+    pub fn series<T: Field + Clone>(&self, name: &str) -> Result<Series<T, String>, RudasError> {
+        self.column(name)?;
+        let data = self
+            .rows()
+            .map(|row| row.get(name))
+            .collect::<Result<_, _>>()?;
+        Ok(Series::from_parts(data, self.label.clone()).with_name(name))
+    }
+
+    pub fn row(&self, position: usize) -> Option<Row<'_>> {
+        (position < self.len()).then_some(Row { df: self, position })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = Row<'_>> {
+        (0..self.len()).map(move |position| Row { df: self, position })
+    }
+
+    pub fn head(&self, n: usize) -> DataFrame {
+        let positions: Vec<usize> = (0..n.min(self.len())).collect();
+        self.take(&positions)
+    }
+
+    pub fn tail(&self, n: usize) -> DataFrame {
+        let positions: Vec<usize> = (self.len() - n.min(self.len())..self.len()).collect();
+        self.take(&positions)
+    }
+
+    /// Summary statistics of every numeric column, one column per source
+    /// column and one row per statistic of `Series::describe`. Missing values
+    /// are excluded and non-numeric columns are skipped.
+    pub fn describe(&self) -> DataFrame {
+        let mut df = DataFrame::new();
+        for (name, column) in &self.columns {
+            if !matches!(column, Column::I64(_) | Column::F64(_)) {
+                continue;
+            }
+            let values: Vec<f64> = (0..column.len()).filter_map(|i| column.f64_at(i)).collect();
+            let stats = Series::from(&values).describe();
+            df.label = stats.label;
+            df.columns.push((name.clone(), Column::from(stats.data)));
+        }
+        df
+    }
+
+    /// The type of every column, labeled by column name.
+    pub fn dtypes(&self) -> Series<DType, String> {
+        Series::from_parts(
+            self.columns
+                .iter()
+                .map(|(_, column)| column.dtype())
+                .collect(),
+            self.columns.iter().map(|(name, _)| name.clone()).collect(),
+        )
+    }
+
+    /// The columns whose type is one of `dtypes`, in their original order.
+    pub fn select_dtypes(&self, dtypes: &[DType]) -> DataFrame {
+        DataFrame {
+            label: self.label.clone(),
+            columns: self
+                .columns
+                .iter()
+                .filter(|(_, column)| dtypes.contains(&column.dtype()))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Converts column `name` to `dtype` in place. Missing values stay
+    /// missing. On failure the error names the first row that cannot be
+    /// converted and the column is left unchanged.
+    pub fn cast_column(&mut self, name: &str, dtype: DType) -> Result<(), RudasError> {
+        let (_, column) = self
+            .columns
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .ok_or_else(|| RudasError::ColumnNotFound(name.to_string()))?;
+        *column = column
+            .cast(dtype)
+            .map_err(|(i, message)| RudasError::Cast {
+                column: name.to_string(),
+                label: self.label[i].clone(),
+                message,
+            })?;
+        Ok(())
+    }
+
+    /// Pearson correlation of every pair of numeric columns, as a square
+    /// frame labeled and named by those columns. Each pair uses the rows
+    /// where both cells are present and not `NAN`.
+    pub fn corr(&self) -> DataFrame {
+        let numeric: Vec<(&String, Vec<Option<f64>>)> = self
+            .columns
+            .iter()
+            .filter(|(_, column)| column.dtype().is_numeric())
+            .map(|(name, column)| {
+                let values = (0..column.len())
+                    .map(|i| column.f64_at(i).filter(|x| !x.is_nan()))
+                    .collect();
+                (name, values)
+            })
+            .collect();
+        DataFrame {
+            label: numeric.iter().map(|(name, _)| name.to_string()).collect(),
+            columns: numeric
+                .iter()
+                .map(|(name, x)| {
+                    let column = numeric.iter().map(|(_, y)| Some(pearson(x, y))).collect();
+                    (name.to_string(), Column::F64(column))
+                })
+                .collect(),
+        }
+    }
+
+    /// Builds a new frame by transforming every column. Each result must keep
+    /// the length of the frame.
+    pub fn apply_columns<F>(&self, f: F) -> Result<DataFrame, RudasError>
+    where
+        F: Fn(&str, &Column) -> Column,
+    {
+        let mut df = DataFrame {
+            label: self.label.clone(),
+            columns: Vec::new(),
+        };
+        for (name, column) in &self.columns {
+            df.add_column(name, f(name, column))?;
+        }
+        Ok(df)
+    }
+
+    /// Computes a new column by calling `f` on a borrowed view of every row.
+    /// The first error returned by `f` is propagated.
+    pub fn apply_rows<T, F>(&self, f: F) -> Result<Column, RudasError>
+    where
+        T: Field,
+        F: Fn(&Row<'_>) -> Result<T, RudasError>,
+    {
+        let mut column = T::empty_column();
+        for row in self.rows() {
+            f(&row)?.push_to(&mut column);
+        }
+        Ok(column)
+    }
+
+    /// Sorts the rows by the values of `column`, keeping each row intact
+    /// across all columns and the labels. The sort is stable and missing
+    /// values are placed last.
+    pub fn sort_by(&self, column: &str, ascending: bool) -> Result<DataFrame, RudasError> {
+        self.sort_values(&[column], &[ascending])
+    }
+
+    /// Sorts the rows by several columns, comparing by `by[0]` first and
+    /// falling back to the next column on ties, each in its own direction.
+    /// The sort is stable and missing values, including `NAN`, are placed
+    /// last.
+    pub fn sort_values(&self, by: &[&str], ascending: &[bool]) -> Result<DataFrame, RudasError> {
+        let cmp = self.row_comparator(by, ascending)?;
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by(|&a, &b| cmp(a, b));
+        Ok(self.take(&order))
+    }
+
+    /// The `n` rows with the largest values of `column`, in the order of
+    /// `sort_by(column, false).head(n)` but without sorting every row.
+    pub fn nlargest(&self, n: usize, column: &str) -> Result<DataFrame, RudasError> {
+        self.top(n, column, false)
+    }
+
+    /// The `n` rows with the smallest values of `column`, in the order of
+    /// `sort_by(column, true).head(n)` but without sorting every row.
+    pub fn nsmallest(&self, n: usize, column: &str) -> Result<DataFrame, RudasError> {
+        self.top(n, column, true)
+    }
+
+    fn top(&self, n: usize, column: &str, ascending: bool) -> Result<DataFrame, RudasError> {
+        let key = self.column(column)?;
+        let cmp = |a: &usize, b: &usize| key.compare(*a, *b, ascending).then(a.cmp(b));
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        let n = n.min(order.len());
+        if n < order.len() {
+            order.select_nth_unstable_by(n, cmp);
+            order.truncate(n);
+        }
+        order.sort_by(cmp);
+        Ok(self.take(&order))
+    }
+
+    fn row_comparator(
+        &self,
+        by: &[&str],
+        ascending: &[bool],
+    ) -> Result<impl Fn(usize, usize) -> Ordering + '_, RudasError> {
+        if by.len() != ascending.len() {
+            return Err(RudasError::LengthMismatch {
+                expected: by.len(),
+                found: ascending.len(),
+            });
+        }
+        let keys = by
+            .iter()
+            .map(|name| self.column(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let ascending = ascending.to_vec();
+        Ok(move |a, b| {
+            keys.iter()
+                .zip(&ascending)
+                .map(|(key, &asc)| key.compare(a, b, asc))
+                .find(|ord| ord.is_ne())
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+
+    pub(crate) fn take(&self, positions: &[usize]) -> DataFrame {
+        DataFrame {
+            label: positions.iter().map(|&p| self.label[p].clone()).collect(),
+            columns: self
+                .columns
+                .iter()
+                .map(|(name, column)| (name.clone(), column.take(positions)))
+                .collect(),
+        }
+    }
+
+    /// Reshapes a long frame into a wide one: each distinct value of `columns`
+    /// becomes a column, each distinct value of `index` becomes a row label and
+    /// the cells are taken from `values`.
+    ///
+    /// Rows and columns are ordered by first appearance. Rows whose `index` or
+    /// `columns` cell is missing are ignored, missing combinations are filled
+    /// with missing values and a duplicated `(index, columns)` pair is an error.
+    pub fn pivot(&self, index: &str, columns: &str, values: &str) -> Result<DataFrame, RudasError> {
+        let source = self.column(values)?;
+        let groups = self.pivot_groups(index, columns)?;
+
+        let mut df = DataFrame::new();
+        df.label = groups.rows;
+        for (name, cells) in groups.columns.iter().zip(groups.cells) {
+            let mut positions = Vec::with_capacity(cells.len());
+            for (row, cell) in cells.iter().enumerate() {
+                if cell.len() > 1 {
+                    return Err(RudasError::DuplicateEntry(format!(
+                        "({}, {})",
+                        df.label[row], name
+                    )));
+                }
+                positions.push(cell.first().copied());
+            }
+            df.columns
+                .push((name.clone(), source.take_or_null(&positions)));
+        }
+        Ok(df)
+    }
+
+    /// Like `pivot`, but duplicated `(index, columns)` pairs are combined with
+    /// `agg`. Missing cells of `values` are skipped while aggregating.
+    ///
+    /// `Mean` always yields `f64` columns, `Sum` keeps the numeric type of
+    /// `values` and `Count` yields `i64` columns. Fails when an `i64` sum
+    /// overflows.
+    pub fn pivot_table(
+        &self,
+        index: &str,
+        columns: &str,
+        values: &str,
+        agg: Aggregation,
+    ) -> Result<DataFrame, RudasError> {
+        let source = self.column(values)?;
+        let groups = self.pivot_groups(index, columns)?;
+
+        let mut df = DataFrame::new();
+        df.label = groups.rows;
+        for (name, cells) in groups.columns.into_iter().zip(groups.cells) {
+            df.columns
+                .push((name, aggregate(source, values, &cells, agg)?));
+        }
+        Ok(df)
+    }
+
+    fn pivot_groups(&self, index: &str, columns: &str) -> Result<PivotGroups, RudasError> {
+        let index = self.column(index)?;
+        let columns = self.column(columns)?;
+
+        let mut rows = Vec::new();
+        let mut row_pos = HashMap::new();
+        let mut names = Vec::new();
+        let mut name_pos = HashMap::new();
+        let mut cells: Vec<Vec<Vec<usize>>> = Vec::new();
+
+        for i in 0..self.len() {
+            let (row, name) = match (index.get(i), columns.get(i)) {
+                (Some(row), Some(name)) => (row.to_string(), name.to_string()),
+                _ => continue,
+            };
+            let r = *row_pos.entry(row.clone()).or_insert_with(|| {
+                rows.push(row);
+                for c in cells.iter_mut() {
+                    c.push(Vec::new());
+                }
+                rows.len() - 1
+            });
+            let c = *name_pos.entry(name.clone()).or_insert_with(|| {
+                names.push(name);
+                cells.push(vec![Vec::new(); rows.len()]);
+                names.len() - 1
+            });
+            cells[c][r].push(i);
+        }
+
+        Ok(PivotGroups {
+            rows,
+            columns: names,
+            cells,
+        })
+    }
+}
+
+impl<T: Field + Clone, U: PartialEq + Clone + Display> Series<T, U> {
+    /// Converts the series into a single-column frame, labeling the rows by
+    /// the text of the series labels.
+    pub fn to_frame(&self, name: &str) -> DataFrame {
+        let mut column = T::empty_column();
+        for value in &self.data {
+            value.push_to(&mut column);
+        }
+        DataFrame {
+            label: self.label.iter().map(|l| l.to_string()).collect(),
+            columns: vec![(name.to_string(), column)],
+        }
+    }
+}
+
+/// A borrowed view of a single row of a `DataFrame`.
+#[derive(Debug, Clone, Copy)]
+pub struct Row<'a> {
+    df: &'a DataFrame,
+    position: usize,
+}
+
+impl<'a> Row<'a> {
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn label(&self) -> &'a str {
+        &self.df.label[self.position]
+    }
+
+    pub fn column(&self, name: &str) -> Result<&'a Column, RudasError> {
+        self.df.column(name)
+    }
+
+    pub fn value(&self, name: &str) -> Result<Option<Value>, RudasError> {
+        Ok(self.column(name)?.get(self.position))
+    }
+
+    pub fn get<F: Field>(&self, name: &str) -> Result<F, RudasError> {
+        F::read(self, name)
+    }
+
+    pub fn get_i64(&self, name: &str) -> Result<i64, RudasError> {
+        self.get(name)
+    }
+
+    /// Reads a numeric cell as `f64`, accepting both `f64` and `i64` columns.
+    pub fn get_f64(&self, name: &str) -> Result<f64, RudasError> {
+        let column = self.column(name)?;
+        match column {
+            Column::I64(_) | Column::F64(_) => {
+                column
+                    .f64_at(self.position)
+                    .ok_or_else(|| RudasError::MissingValue {
+                        column: name.to_string(),
+                        label: self.label().to_string(),
+                    })
+            }
+            _ => Err(RudasError::TypeMismatch {
+                column: name.to_string(),
+                expected: "numeric",
+            }),
+        }
+    }
+
+    pub fn get_bool(&self, name: &str) -> Result<bool, RudasError> {
+        self.get(name)
+    }
+
+    pub fn get_str(&self, name: &str) -> Result<&'a str, RudasError> {
+        match self.column(name)? {
+            Column::Str(v) => v[self.position]
+                .as_deref()
+                .ok_or_else(|| RudasError::MissingValue {
+                    column: name.to_string(),
+                    label: self.label().to_string(),
+                }),
+            _ => Err(RudasError::TypeMismatch {
+                column: name.to_string(),
+                expected: "str",
+            }),
+        }
+    }
+}
+
+/// Correlation over the positions where both values are present. `NAN` when
+/// there are fewer than two such positions or either side is constant.
+pub(crate) fn pearson(x: &[Option<f64>], y: &[Option<f64>]) -> f64 {
+    let pairs: Vec<(f64, f64)> = x
+        .iter()
+        .zip(y)
+        .filter_map(|(a, b)| Some(((*a)?, (*b)?)))
+        .collect();
+    let n = pairs.len() as f64;
+    if pairs.len() < 2 {
+        return f64::NAN;
+    }
+    let mean_x = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (a, b) in pairs {
+        sxy += (a - mean_x) * (b - mean_y);
+        sxx += (a - mean_x).powi(2);
+        syy += (b - mean_y).powi(2);
+    }
+    if sxx == 0.0 || syy == 0.0 {
+        return f64::NAN;
+    }
+    (sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0)
+}
+
+fn compare_cells<T: PartialOrd>(a: Option<&T>, b: Option<&T>, ascending: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let ord = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+            if ascending {
+                ord
+            } else {
+                ord.reverse()
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+struct PivotGroups {
+    rows: Vec<String>,
+    columns: Vec<String>,
+    cells: Vec<Vec<Vec<usize>>>,
+}
+
+fn aggregate(
+    source: &Column,
+    name: &str,
+    cells: &[Vec<usize>],
+    agg: Aggregation,
+) -> Result<Column, RudasError> {
+    let column = match (agg, source) {
+        (Aggregation::Count, _) => Column::I64(
+            cells
+                .iter()
+                .map(|cell| {
+                    (!cell.is_empty())
+                        .then(|| cell.iter().filter(|&&p| !source.is_null(p)).count() as i64)
+                })
+                .collect(),
+        ),
+        (Aggregation::Sum, Column::I64(v)) => Column::I64(
+            cells
+                .iter()
+                .map(|cell| {
+                    if cell.is_empty() {
+                        return Ok(None);
+                    }
+                    cell.iter()
+                        .filter_map(|&p| v[p])
+                        .try_fold(0i64, |acc, x| acc.checked_add(x))
+                        .map(Some)
+                        .ok_or_else(|| {
+                            RudasError::InvalidArgument(format!(
+                                "sum of column {} overflows i64",
+                                name
+                            ))
+                        })
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        (Aggregation::Sum, Column::F64(v)) => Column::F64(
+            cells
+                .iter()
+                .map(|cell| (!cell.is_empty()).then(|| cell.iter().filter_map(|&p| v[p]).sum()))
+                .collect(),
+        ),
+        (Aggregation::Mean, Column::I64(_) | Column::F64(_)) => Column::F64(
+            cells
+                .iter()
+                .map(|cell| {
+                    let present: Vec<f64> = cell.iter().filter_map(|&p| source.f64_at(p)).collect();
+                    (!present.is_empty())
+                        .then(|| present.iter().sum::<f64>() / present.len() as f64)
+                })
+                .collect(),
+        ),
+        _ => {
+            return Err(RudasError::TypeMismatch {
+                column: name.to_string(),
+                expected: "numeric",
+            })
+        }
+    };
+    Ok(column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One row per event, as a tidy log of `(day, category, count)`.
+    fn event_log() -> DataFrame {
+        DataFrame::from_columns(vec![
+            (
+                "day",
+                Column::from(vec!["mon", "mon", "tue", "tue", "wed", "mon"]),
+            ),
+            ("category", Column::from(vec!["b", "a", "a", "a", "b", "b"])),
+            ("count", Column::from(vec![1i64, 2, 3, 4, 5, 6])),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn pivot_table_turns_an_event_log_into_a_matrix() {
+        let df = event_log();
+        let sums = df
+            .pivot_table("day", "category", "count", Aggregation::Sum)
+            .unwrap();
+        assert_eq!(sums.label(), &["mon", "tue", "wed"]);
+        assert_eq!(sums.column_names(), vec!["b", "a"]);
+        assert_eq!(
+            sums.column("a").unwrap(),
+            &Column::I64(vec![Some(2), Some(7), None])
+        );
+        assert_eq!(
+            sums.column("b").unwrap(),
+            &Column::I64(vec![Some(7), None, Some(5)])
+        );
+
+        let means = df
+            .pivot_table("day", "category", "count", Aggregation::Mean)
+            .unwrap();
+        assert_eq!(
+            means.column("a").unwrap(),
+            &Column::F64(vec![Some(2.0), Some(3.5), None])
+        );
+        let counts = df
+            .pivot_table("day", "category", "count", Aggregation::Count)
+            .unwrap();
+        assert_eq!(
+            counts.column("b").unwrap(),
+            &Column::I64(vec![Some(2), None, Some(1)])
+        );
+    }
+
+    #[test]
+    fn pivot_rejects_duplicated_pairs() {
+        let df = event_log();
+        assert!(matches!(
+            df.pivot("day", "category", "count"),
+            Err(RudasError::DuplicateEntry(_))
+        ));
+        let unique = df.head(3).pivot("day", "category", "count").unwrap();
+        assert_eq!(unique.label(), &["mon", "tue"]);
+        assert_eq!(
+            unique.column("b").unwrap(),
+            &Column::I64(vec![Some(1), None])
+        );
+        assert!(matches!(
+            df.pivot_table("day", "count", "category", Aggregation::Sum),
+            Err(RudasError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn sort_by_keeps_rows_together_and_puts_missing_last() {
+        let df = DataFrame::from_columns(vec![
+            (
+                "key",
+                Column::F64(vec![Some(2.0), None, Some(f64::NAN), Some(1.0)]),
+            ),
+            ("name", Column::from(vec!["a", "b", "c", "d"])),
+        ])
+        .unwrap();
+        let sorted = df.sort_by("key", true).unwrap();
+        assert_eq!(sorted.label(), &["3", "0", "1", "2"]);
+        assert_eq!(
+            sorted.column("name").unwrap(),
+            &Column::from(vec!["d", "a", "b", "c"])
+        );
+        assert_eq!(
+            df.sort_by("key", false).unwrap().label(),
+            &["0", "3", "1", "2"]
+        );
+        assert!(matches!(
+            df.sort_by("missing", true),
+            Err(RudasError::ColumnNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn head_tail_and_describe() {
+        let df = DataFrame::from_columns(vec![
+            ("a", Column::from(vec![1i64, 2, 3, 4])),
+            ("s", Column::from(vec!["a", "b", "c", "d"])),
+            (
+                "f",
+                Column::F64(vec![Some(1.0), None, Some(3.0), Some(5.0)]),
+            ),
+        ])
+        .unwrap();
+        assert_eq!(df.head(2).label(), &["0", "1"]);
+        assert_eq!(df.tail(3).label(), &["1", "2", "3"]);
+        assert_eq!(df.tail(10).len(), 4);
+
+        let stats = df.describe();
+        assert_eq!(stats.column_names(), vec!["a", "f"]);
+        assert_eq!(stats.label()[0], "count");
+        assert_eq!(stats.column("f").unwrap().f64_at(0), Some(3.0));
+        assert_eq!(stats.column("a").unwrap().f64_at(5), Some(2.5));
+    }
+
+    #[test]
+    fn apply_rows_and_columns() {
+        let mut df = DataFrame::from_columns(vec![
+            ("price", Column::from(vec![1.5, 2.0])),
+            ("qty", Column::from(vec![2i64, 3])),
+            ("s", Column::from(vec!["a", "b"])),
+        ])
+        .unwrap();
+        let total = df
+            .apply_rows(|r| Ok(r.get_f64("price")? * r.get_f64("qty")?))
+            .unwrap();
+        assert_eq!(total, Column::from(vec![3.0, 6.0]));
+        df.add_column("total", total).unwrap();
+        assert!(df.apply_rows(|r| r.get_f64("s")).is_err());
+        assert_eq!(
+            df.apply_rows(|r| Ok(r.get_str("s")?.to_uppercase()))
+                .unwrap(),
+            Column::from(vec!["A", "B"])
+        );
+
+        assert_eq!(df.apply_columns(|_, c| c.clone()).unwrap(), df);
+        assert!(df.apply_columns(|_, _| Column::from(vec![1i64])).is_err());
+    }
+
+    #[test]
+    fn sort_values_by_a_string_and_a_float_column() {
+        let df = DataFrame::from_columns(vec![
+            ("s", Column::from(vec!["b", "a", "b", "a", "b"])),
+            ("f", Column::from(vec![1.0, f64::NAN, 3.0, 2.0, 1.0])),
+        ])
+        .unwrap();
+        let sorted = df.sort_values(&["s", "f"], &[true, false]).unwrap();
+        assert_eq!(sorted.label(), &["3", "1", "2", "0", "4"]);
+        let sorted = df.sort_values(&["s", "f"], &[false, true]).unwrap();
+        assert_eq!(sorted.label(), &["0", "4", "2", "3", "1"]);
+        assert!(df.sort_values(&["s"], &[]).is_err());
+        assert!(df.sort_values(&["zz"], &[true]).is_err());
+    }
+
+    #[test]
+    fn nlargest_and_nsmallest_put_nan_last() {
+        let df = DataFrame::from_columns(vec![(
+            "f",
+            Column::from(vec![1.0, f64::NAN, 3.0, 2.0, 1.0]),
+        )])
+        .unwrap();
+        assert_eq!(df.nlargest(2, "f").unwrap().label(), &["2", "3"]);
+        assert_eq!(df.nsmallest(3, "f").unwrap().label(), &["0", "4", "3"]);
+        assert_eq!(
+            df.nsmallest(10, "f").unwrap().label(),
+            df.sort_by("f", true).unwrap().label()
+        );
+        assert_eq!(df.nsmallest(0, "f").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn dtypes_select_and_cast_columns() {
+        let mut df = DataFrame::from_columns(vec![
+            ("a", Column::from(vec![1i64, 2, 3, 4])),
+            ("b", Column::from(vec![2.0, 4.0, 6.0, 8.5])),
+            (
+                "c",
+                Column::from(vec![Some(4.0), Some(3.0), None, Some(1.0)]),
+            ),
+            ("s", Column::from(vec!["1", "2", "x", "4"])),
+            ("t", Column::from(vec![true, false, true, true])),
+        ])
+        .unwrap();
+        let dtypes = df.dtypes();
+        assert_eq!(
+            dtypes.data,
+            vec![DType::I64, DType::F64, DType::F64, DType::Str, DType::Bool]
+        );
+        assert_eq!(dtypes.label, vec!["a", "b", "c", "s", "t"]);
+        assert_eq!(
+            df.select_dtypes(&[DType::I64, DType::F64]).column_names(),
+            vec!["a", "b", "c"]
+        );
+
+        let err = df.cast_column("s", DType::I64).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cast error : column s at label 2 : cannot cast 'x' to i64"
+        );
+        assert_eq!(df.column("s").unwrap().dtype(), DType::Str);
+        df.cast_column("t", DType::F64).unwrap();
+        assert_eq!(
+            df.column("t").unwrap(),
+            &Column::from(vec![1.0, 0.0, 1.0, 1.0])
+        );
+        df.cast_column("b", DType::Str).unwrap();
+        assert_eq!(
+            df.column("b").unwrap(),
+            &Column::from(vec!["2.0", "4.0", "6.0", "8.5"])
+        );
+        assert!(df.cast_column("zz", DType::Str).is_err());
+        df.cast_column("c", DType::I64).unwrap();
+        assert_eq!(
+            df.column("c").unwrap(),
+            &Column::I64(vec![Some(4), Some(3), None, Some(1)])
+        );
+        let mut fractional =
+            DataFrame::from_columns(vec![("f", Column::from(vec![Some(1.0), None, Some(2.5)]))])
+                .unwrap();
+        assert!(fractional.cast_column("f", DType::I64).is_err());
+    }
+
+    #[test]
+    fn corr_builds_a_matrix_of_numeric_columns() {
+        let df = DataFrame::from_columns(vec![
+            ("a", Column::from(vec![1i64, 2, 3, 4])),
+            ("b", Column::from(vec![2.0, 4.0, 6.0, 8.0])),
+            (
+                "c",
+                Column::from(vec![Some(4.0), Some(3.0), None, Some(1.0)]),
+            ),
+            ("s", Column::from(vec!["1", "2", "x", "4"])),
+        ])
+        .unwrap();
+        let corr = df.corr();
+        assert_eq!(corr.column_names(), vec!["a", "b", "c"]);
+        assert_eq!(corr.label(), &["a", "b", "c"]);
+        let first = corr.row(0).unwrap();
+        assert!((first.get_f64("a").unwrap() - 1.0).abs() < 1e-12);
+        assert!((first.get_f64("b").unwrap() - 1.0).abs() < 1e-12);
+        assert!((first.get_f64("c").unwrap() + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pivot_table_sum_reports_overflow() {
+        let df = |counts: Vec<i64>| {
+            DataFrame::from_columns(vec![
+                ("day", Column::from(vec!["mon", "mon", "tue"])),
+                ("category", Column::from(vec!["a", "a", "a"])),
+                ("count", Column::from(counts)),
+            ])
+            .unwrap()
+        };
+        let near = df(vec![i64::MAX - 1, 1, i64::MAX])
+            .pivot_table("day", "category", "count", Aggregation::Sum)
+            .unwrap();
+        assert_eq!(
+            near.column("a").unwrap(),
+            &Column::I64(vec![Some(i64::MAX), Some(i64::MAX)])
+        );
+        assert!(matches!(
+            df(vec![i64::MAX, 1, 0]).pivot_table("day", "category", "count", Aggregation::Sum),
+            Err(RudasError::InvalidArgument(_))
+        ));
+    }
+}
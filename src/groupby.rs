@@ -0,0 +1,343 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{data::Series, error::RudasError, num::Numeric};
+
+/// Values that can be missing, which `GroupBy::first` and `GroupBy::last`
+/// skip. `None` is the only missing value of the types implemented here;
+/// `NAN` is not missing. Other types can opt in with an empty
+/// `impl Missing for MyType {}`, which never reports a missing value.
+pub trait Missing {
+    fn is_missing(&self) -> bool {
+        false
+    }
+}
+
+impl<T> Missing for Option<T> {
+    fn is_missing(&self) -> bool {
+        self.is_none()
+    }
+}
+
+macro_rules! impl_missing {
+    ($($t:ty),*) => {
+        $(impl Missing for $t {})*
+    };
+}
+
+impl_missing!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char, String,
+    &str
+);
+
+impl<T> Missing for Vec<T> {}
+
+/// Elements of a `Series` split by key. The groups are in the order their
+/// keys first appear and the positions within a group are increasing.
+pub struct GroupBy<'a, T: Clone, U: PartialEq + Clone, K> {
+    series: &'a Series<T, U>,
+    keys: Vec<K>,
+    groups: Vec<Vec<usize>>,
+}
+
+impl<T: Clone, U: PartialEq + Clone> Series<T, U> {
+    /// Groups the elements by `keys`, one key per element.
+    pub fn groupby<K: Hash + Eq + Clone>(
+        &self,
+        keys: &[K],
+    ) -> Result<GroupBy<'_, T, U, K>, RudasError> {
+        if keys.len() != self.len() {
+            return Err(RudasError::LengthMismatch {
+                expected: self.len(),
+                found: keys.len(),
+            });
+        }
+        let mut index: HashMap<&K, usize> = HashMap::new();
+        let mut groupby = GroupBy {
+            series: self,
+            keys: Vec::new(),
+            groups: Vec::new(),
+        };
+        for (i, key) in keys.iter().enumerate() {
+            let group = *index.entry(key).or_insert_with(|| {
+                groupby.keys.push(key.clone());
+                groupby.groups.push(Vec::new());
+                groupby.groups.len() - 1
+            });
+            groupby.groups[group].push(i);
+        }
+        Ok(groupby)
+    }
+}
+
+impl<'a, T: Clone, U: PartialEq + Clone, K: PartialEq + Clone> GroupBy<'a, T, U, K> {
+    /// Number of groups.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Positions of the elements of every group, in the order of `keys`.
+    pub fn indices(&self) -> &[Vec<usize>] {
+        &self.groups
+    }
+
+    pub fn get_group(&self, key: &K) -> Option<Series<T, U>> {
+        let group = self.keys.iter().position(|k| k == key)?;
+        Some(self.subset(&self.groups[group]))
+    }
+
+    pub(crate) fn subset(&self, positions: &[usize]) -> Series<T, U> {
+        self.series.with_parts(
+            positions
+                .iter()
+                .map(|&i| self.series.data[i].clone())
+                .collect(),
+            positions
+                .iter()
+                .map(|&i| self.series.label[i].clone())
+                .collect(),
+        )
+    }
+
+    /// The first value of every group that is not missing, labeled by key.
+    /// A group with only missing values gives its first one.
+    pub fn first(&self) -> Series<T, K>
+    where
+        T: Missing,
+    {
+        self.first_with(false)
+    }
+
+    /// The last value of every group that is not missing, labeled by key.
+    /// A group with only missing values gives its last one.
+    pub fn last(&self) -> Series<T, K>
+    where
+        T: Missing,
+    {
+        self.last_with(false)
+    }
+
+    /// Like `first`, but with `include_missing` the first element is taken
+    /// even when it is missing.
+    pub fn first_with(&self, include_missing: bool) -> Series<T, K>
+    where
+        T: Missing,
+    {
+        self.pick(|positions| {
+            let mut present = positions.iter().copied();
+            present
+                .find(|&i| include_missing || !self.series.data[i].is_missing())
+                .or(positions.first().copied())
+        })
+    }
+
+    /// Like `last`, but with `include_missing` the last element is taken
+    /// even when it is missing.
+    pub fn last_with(&self, include_missing: bool) -> Series<T, K>
+    where
+        T: Missing,
+    {
+        self.pick(|positions| {
+            let mut present = positions.iter().copied();
+            present
+                .rfind(|&i| include_missing || !self.series.data[i].is_missing())
+                .or(positions.last().copied())
+        })
+    }
+
+    /// The element at position `n` within every group, labeled by key.
+    /// Groups with `n` or fewer elements are left out.
+    pub fn nth(&self, n: usize) -> Series<T, K> {
+        self.pick(|positions| positions.get(n).copied())
+    }
+
+    /// The element `n` positions before the last one of every group, labeled
+    /// by key. Groups with `n` or fewer elements are left out.
+    pub fn nth_from_end(&self, n: usize) -> Series<T, K> {
+        self.pick(|positions| {
+            let i = positions.len().checked_sub(n + 1)?;
+            Some(positions[i])
+        })
+    }
+
+    fn pick(&self, f: impl Fn(&[usize]) -> Option<usize>) -> Series<T, K> {
+        let mut out = Series {
+            name: self.series.name.clone(),
+            ..Series::from_parts(Vec::new(), Vec::new())
+        };
+        for (key, positions) in self.keys.iter().zip(&self.groups) {
+            if let Some(i) = f(positions) {
+                out.data.push(self.series.data[i].clone());
+                out.label.push(key.clone());
+            }
+        }
+        out
+    }
+
+    /// Reduces every group with `f`. The result is labeled by key.
+    pub fn agg<R: Clone, F: Fn(&Series<T, U>) -> R>(&self, f: F) -> Series<R, K> {
+        self.by_key(
+            self.groups
+                .iter()
+                .map(|positions| f(&self.subset(positions)))
+                .collect(),
+        )
+    }
+
+    /// Labels one value per group by key, keeping the name of the series.
+    pub(crate) fn by_key<R: Clone>(&self, data: Vec<R>) -> Series<R, K> {
+        Series {
+            name: self.series.name.clone(),
+            ..Series::from_parts(data, self.keys.clone())
+        }
+    }
+
+    /// Number of elements in every group.
+    pub fn size(&self) -> Series<usize, K> {
+        Series::from_parts(
+            self.groups.iter().map(Vec::len).collect(),
+            self.keys.clone(),
+        )
+    }
+
+    /// Applies `f` to every group and puts its output back at the positions
+    /// of the group, so the result has the labels and order of the original
+    /// series. `f` must return one value per element of the group.
+    pub fn transform<R: Clone, F: Fn(&Series<T, U>) -> Vec<R>>(
+        &self,
+        f: F,
+    ) -> Result<Series<R, U>, RudasError> {
+        let mut data: Vec<Option<R>> = vec![None; self.series.len()];
+        for positions in &self.groups {
+            let values = f(&self.subset(positions));
+            if values.len() != positions.len() {
+                return Err(RudasError::LengthMismatch {
+                    expected: positions.len(),
+                    found: values.len(),
+                });
+            }
+            for (&i, value) in positions.iter().zip(values) {
+                data[i] = Some(value);
+            }
+        }
+        Ok(self.series.with_data(data.into_iter().flatten().collect()))
+    }
+
+    /// Keeps the elements of the groups for which `f` returns true, in their
+    /// original order.
+    pub fn filter<F: Fn(&Series<T, U>) -> bool>(&self, f: F) -> Series<T, U> {
+        let mut positions: Vec<usize> = self
+            .groups
+            .iter()
+            .filter(|positions| f(&self.subset(positions)))
+            .flatten()
+            .copied()
+            .collect();
+        positions.sort_unstable();
+        self.subset(&positions)
+    }
+}
+
+impl<'a, T: Numeric, U: PartialEq + Clone, K: PartialEq + Clone> GroupBy<'a, T, U, K> {
+    pub fn sum(&self) -> Series<T, K> {
+        self.agg(|group| group.sum())
+    }
+
+    pub fn mean(&self) -> Series<f64, K> {
+        self.agg(|group| group.mean())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events() -> Series<Option<i32>, &'static str> {
+        Series::from_label(
+            &[Some(1), None, Some(3), None, Some(5), None],
+            &["a", "b", "c", "d", "e", "f"],
+        )
+    }
+
+    #[test]
+    fn first_and_last_skip_missing_values() {
+        let s = events();
+        let g = s.groupby(&["x", "y", "x", "x", "z", "z"]).unwrap();
+        assert_eq!(g.first().label, vec!["x", "y", "z"]);
+        assert_eq!(g.first().data, vec![Some(1), None, Some(5)]);
+        assert_eq!(g.last().data, vec![Some(3), None, Some(5)]);
+        assert_eq!(g.first_with(true).data, vec![Some(1), None, Some(5)]);
+        assert_eq!(g.last_with(true).data, vec![None, None, None]);
+
+        let g = s.groupby(&["x", "x", "x", "y", "y", "y"]).unwrap();
+        assert_eq!(g.first().data, vec![Some(1), Some(5)]);
+        assert_eq!(g.first_with(true).data, vec![Some(1), None]);
+    }
+
+    #[test]
+    fn first_and_last_keep_plain_values() {
+        let s = Series::from(&[10, 20, 30, 40]);
+        let g = s.groupby(&[1, 2, 1, 2]).unwrap();
+        assert_eq!(g.first().data, vec![10, 20]);
+        assert_eq!(g.last().data, vec![30, 40]);
+    }
+
+    #[test]
+    fn nth_picks_positions_within_groups() {
+        let s = events();
+        let g = s.groupby(&["x", "y", "x", "x", "z", "z"]).unwrap();
+        let n = g.nth(1);
+        assert_eq!(n.label, vec!["x", "z"]);
+        assert_eq!(n.data, vec![Some(3), None]);
+        assert_eq!(g.nth_from_end(2).data, vec![Some(1)]);
+        assert!(g.nth(5).is_empty());
+    }
+
+    #[test]
+    fn series_nth_returns_label_and_value() {
+        let s = events();
+        assert_eq!(s.first(), Some((&"a", &Some(1))));
+        assert_eq!(s.last(), Some((&"f", &None)));
+        assert_eq!(s.nth(1), Some((&"b", &None)));
+        assert_eq!(s.nth_from_end(1), Some((&"e", &Some(5))));
+        assert_eq!(s.nth(9), None);
+        assert_eq!(s.nth_from_end(6), None);
+    }
+
+    #[test]
+    fn transform_keeps_the_order_and_labels_of_the_input() {
+        let s = Series::from_label(&[1.0, 10.0, 3.0, 20.0, 5.0], &["p", "q", "r", "s", "t"])
+            .with_name("x");
+        let g = s.groupby(&["a", "b", "a", "b", "a"]).unwrap();
+        let centered = g
+            .transform(|group| {
+                let mean = group.mean();
+                group.data.iter().map(|v| v - mean).collect()
+            })
+            .unwrap();
+        assert_eq!(centered.label, s.label);
+        assert_eq!(centered.data, vec![-2.0, -5.0, 0.0, 5.0, 2.0]);
+        assert_eq!(centered.name.as_deref(), Some("x"));
+        assert!(matches!(
+            g.transform(|_| vec![0.0]),
+            Err(RudasError::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn filter_keeps_whole_groups_in_order() {
+        let s = Series::from_label(&[1, 2, 3, 4, 5], &["p", "q", "r", "s", "t"]);
+        let g = s.groupby(&["a", "b", "a", "c", "a"]).unwrap();
+        let kept = g.filter(|group| group.len() >= 2);
+        assert_eq!(kept.as_parts(), (&[1, 3, 5][..], &["p", "r", "t"][..]));
+        assert!(g.filter(|_| false).is_empty());
+        assert_eq!(g.filter(|_| true).as_parts(), s.as_parts());
+    }
+}
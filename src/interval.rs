@@ -0,0 +1,126 @@
+use std::fmt::Display;
+
+use crate::{data::Series, num::Numeric};
+
+/// Which ends of an `Interval` belong to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Closed {
+    Left,
+    Right,
+    Both,
+    Neither,
+}
+
+/// A range of values usable as a label, such as the buckets of
+/// `Series::histogram`, `Series::cut_intervals` and
+/// `Series::qcut_with_edges`. Intervals order by lower bound, then upper
+/// bound.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Interval<T> {
+    pub lower: T,
+    pub upper: T,
+    pub closed: Closed,
+}
+
+impl<T: PartialOrd> Interval<T> {
+    pub fn new(lower: T, upper: T, closed: Closed) -> Interval<T> {
+        Interval {
+            lower,
+            upper,
+            closed,
+        }
+    }
+
+    fn above_lower(&self, x: &T) -> bool {
+        match self.closed {
+            Closed::Left | Closed::Both => *x >= self.lower,
+            Closed::Right | Closed::Neither => *x > self.lower,
+        }
+    }
+
+    fn below_upper(&self, x: &T) -> bool {
+        match self.closed {
+            Closed::Right | Closed::Both => *x <= self.upper,
+            Closed::Left | Closed::Neither => *x < self.upper,
+        }
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.above_lower(x) && self.below_upper(x)
+    }
+}
+
+impl<T: Numeric> Interval<T> {
+    pub fn width(&self) -> T {
+        self.upper - self.lower
+    }
+
+    pub fn mid(&self) -> f64 {
+        (self.lower.to_f64() + self.upper.to_f64()) / 2.0
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (open, close) = match self.closed {
+            Closed::Left => ('[', ')'),
+            Closed::Right => ('(', ']'),
+            Closed::Both => ('[', ']'),
+            Closed::Neither => ('(', ')'),
+        };
+        write!(f, "{}{}, {}{}", open, self.lower, self.upper, close)
+    }
+}
+
+impl<T: Clone, I: PartialOrd + Clone> Series<T, Interval<I>> {
+    /// Position of the interval label containing `x`, found by binary
+    /// search. The labels must be sorted and must not overlap.
+    pub fn find_interval(&self, x: &I) -> Option<usize> {
+        let i = self
+            .label
+            .partition_point(|interval| !interval.below_upper(x));
+        self.label
+            .get(i)
+            .filter(|interval| interval.contains(x))
+            .map(|_| i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_helpers() {
+        let iv = Interval::new(0, 10, Closed::Right);
+        assert!(!iv.contains(&0) && iv.contains(&10));
+        assert_eq!(iv.width(), 10);
+        assert_eq!(iv.mid(), 5.0);
+        assert_eq!(iv.to_string(), "(0, 10]");
+        assert_eq!(Interval::new(0, 1, Closed::Both).to_string(), "[0, 1]");
+        assert!(Interval::new(0, 5, Closed::Left) < Interval::new(1, 2, Closed::Left));
+    }
+
+    #[test]
+    fn find_interval_searches_sorted_interval_labels() {
+        let s = Series::from(&[0.0, 0.5, 1.0, 1.5, 2.0]);
+        let hist = s.histogram(2).unwrap();
+        assert_eq!(hist.find_interval(&0.0), Some(0));
+        assert_eq!(hist.find_interval(&0.99), Some(0));
+        assert_eq!(hist.find_interval(&1.0), Some(1));
+        assert_eq!(hist.find_interval(&2.0), Some(1));
+        assert_eq!(hist.find_interval(&-0.1), None);
+        assert_eq!(hist.find_interval(&2.1), None);
+
+        let bins = Series::from_label(
+            &["lo", "hi"],
+            &[
+                Interval::new(0, 5, Closed::Right),
+                Interval::new(5, 9, Closed::Right),
+            ],
+        );
+        assert_eq!(bins.find_interval(&5), Some(0));
+        assert_eq!(bins.find_interval(&6), Some(1));
+        assert_eq!(bins.find_interval(&0), None);
+    }
+}
@@ -0,0 +1,642 @@
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+};
+
+use crate::{
+    data::Series,
+    error::RudasError,
+    frame::{Column, DataFrame},
+    record::Field,
+};
+
+/// Layout of a frame in JSON.
+///
+/// `Records` is `[{"col": value, ...}, ...]` and drops the row labels.
+/// `Split` is `{"labels": [...], "columns": {"col": [...], ...}}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonOrient {
+    Records,
+    Split,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+/// Deepest nesting of arrays and objects the parser accepts. A `Split`
+/// document needs three levels; anything deeper is rejected before it can
+/// exhaust the stack.
+const MAX_DEPTH: usize = 3;
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> RudasError {
+        RudasError::Parse {
+            line: self.src[..self.pos].matches('\n').count() + 1,
+            message: message.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), RudasError> {
+        self.skip_whitespace();
+        if self.peek() != Some(byte) {
+            return Err(self.error(&format!("expected '{}'", byte as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Json, RudasError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'"') => Ok(Json::Str(self.string()?)),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'{') => self.nested(Self::object),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<Json, RudasError>,
+    ) -> Result<Json, RudasError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn array(&mut self) -> Result<Json, RudasError> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, RudasError> {
+        self.pos += 1;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(b':')?;
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(members));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn literal(&mut self, text: &str, value: Json) -> Result<Json, RudasError> {
+        if !self.src[self.pos..].starts_with(text) {
+            return Err(self.error("invalid literal"));
+        }
+        self.pos += text.len();
+        Ok(value)
+    }
+
+    /// Skips ASCII digits and returns how many there were.
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn number(&mut self) -> Result<Json, RudasError> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        let int_start = self.pos;
+        let int_digits = self.digits();
+        if int_digits == 0 || (int_digits > 1 && self.src.as_bytes()[int_start] == b'0') {
+            return Err(self.error("invalid number"));
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if self.digits() == 0 {
+                return Err(self.error("invalid number"));
+            }
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if self.digits() == 0 {
+                return Err(self.error("invalid number"));
+            }
+        }
+        let text = &self.src[start..self.pos];
+        if let Ok(v) = text.parse::<i64>() {
+            return Ok(Json::Int(v));
+        }
+        text.parse::<f64>()
+            .map(Json::Float)
+            .map_err(|_| self.error("invalid number"))
+    }
+
+    fn string(&mut self) -> Result<String, RudasError> {
+        if self.peek() != Some(b'"') {
+            return Err(self.error("expected a string"));
+        }
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let rest = &self.src[self.pos..];
+            let end = rest
+                .find(['"', '\\'])
+                .ok_or_else(|| self.error("unterminated string"))?;
+            out.push_str(&rest[..end]);
+            self.pos += end + 1;
+            if rest.as_bytes()[end] == b'"' {
+                return Ok(out);
+            }
+            let escape = self
+                .peek()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.pos += 1;
+            match escape {
+                b'"' => out.push('"'),
+                b'\\' => out.push('\\'),
+                b'/' => out.push('/'),
+                b'b' => out.push('\u{8}'),
+                b'f' => out.push('\u{c}'),
+                b'n' => out.push('\n'),
+                b'r' => out.push('\r'),
+                b't' => out.push('\t'),
+                b'u' => {
+                    let mut code = self.hex4()?;
+                    if (0xd800..0xdc00).contains(&code) && self.src[self.pos..].starts_with("\\u") {
+                        self.pos += 2;
+                        let low = self.hex4()?;
+                        if !(0xdc00..0xe000).contains(&low) {
+                            return Err(self.error("invalid unicode escape"));
+                        }
+                        code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                    }
+                    out.push(
+                        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))?,
+                    );
+                }
+                _ => return Err(self.error("invalid escape")),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, RudasError> {
+        let digits = self
+            .src
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        let code =
+            u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+}
+
+fn parse(src: &str) -> Result<Json, RudasError> {
+    let mut parser = Parser {
+        src,
+        pos: 0,
+        depth: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos != src.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+fn write_string<W: Write>(writer: &mut W, text: &str) -> Result<(), RudasError> {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    writer.write_all(out.as_bytes())?;
+    Ok(())
+}
+
+/// Writes one cell. Missing values and non-finite floats are `null`.
+fn write_cell<W: Write>(writer: &mut W, column: &Column, i: usize) -> Result<(), RudasError> {
+    match column {
+        Column::I64(v) => match v[i] {
+            Some(x) => write!(writer, "{}", x)?,
+            None => write!(writer, "null")?,
+        },
+        Column::F64(v) => match v[i] {
+            Some(x) if x.is_finite() => write!(writer, "{:?}", x)?,
+            _ => write!(writer, "null")?,
+        },
+        Column::Bool(v) => match v[i] {
+            Some(x) => write!(writer, "{}", x)?,
+            None => write!(writer, "null")?,
+        },
+        Column::Str(v) => match &v[i] {
+            Some(x) => write_string(writer, x)?,
+            None => write!(writer, "null")?,
+        },
+    }
+    Ok(())
+}
+
+fn json_column(name: &str, cells: &[&Json]) -> Result<Column, RudasError> {
+    let mismatch = || RudasError::TypeMismatch {
+        column: name.to_string(),
+        expected: "a single JSON type",
+    };
+    let mut kind = None;
+    for cell in cells {
+        let k = match cell {
+            Json::Null => continue,
+            Json::Int(_) | Json::Float(_) => "number",
+            Json::Bool(_) => "bool",
+            Json::Str(_) => "str",
+            Json::Array(_) | Json::Object(_) => {
+                return Err(RudasError::InvalidArgument(format!(
+                    "nested value in column {} is not supported",
+                    name
+                )))
+            }
+        };
+        match kind {
+            None => kind = Some(k),
+            Some(prev) if prev != k => return Err(mismatch()),
+            _ => {}
+        }
+    }
+
+    Ok(match kind {
+        Some("number") if cells.iter().all(|c| !matches!(c, Json::Float(_))) => Column::I64(
+            cells
+                .iter()
+                .map(|c| match c {
+                    Json::Int(x) => Some(*x),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        Some("number") => Column::F64(
+            cells
+                .iter()
+                .map(|c| match c {
+                    Json::Int(x) => Some(*x as f64),
+                    Json::Float(x) => Some(*x),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        Some("bool") => Column::Bool(
+            cells
+                .iter()
+                .map(|c| match c {
+                    Json::Bool(x) => Some(*x),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        _ => Column::Str(
+            cells
+                .iter()
+                .map(|c| match c {
+                    Json::Str(x) => Some(x.clone()),
+                    _ => None,
+                })
+                .collect(),
+        ),
+    })
+}
+
+fn invalid(orient: JsonOrient) -> RudasError {
+    RudasError::InvalidArgument(format!(
+        "document does not match the {:?} orientation",
+        orient
+    ))
+}
+
+impl DataFrame {
+    /// Writes the frame as JSON. Missing values and non-finite floats are
+    /// written as `null`; records are written one row at a time.
+    pub fn to_json<W: Write>(&self, mut writer: W, orient: JsonOrient) -> Result<(), RudasError> {
+        match orient {
+            JsonOrient::Records => {
+                writer.write_all(b"[")?;
+                for i in 0..self.len() {
+                    writer.write_all(if i == 0 { b"{" } else { b",{" })?;
+                    for (j, (name, column)) in self.columns().iter().enumerate() {
+                        if j > 0 {
+                            writer.write_all(b",")?;
+                        }
+                        write_string(&mut writer, name)?;
+                        writer.write_all(b":")?;
+                        write_cell(&mut writer, column, i)?;
+                    }
+                    writer.write_all(b"}")?;
+                }
+                writer.write_all(b"]")?;
+            }
+            JsonOrient::Split => {
+                writer.write_all(b"{\"labels\":[")?;
+                for (i, label) in self.label().iter().enumerate() {
+                    if i > 0 {
+                        writer.write_all(b",")?;
+                    }
+                    write_string(&mut writer, label)?;
+                }
+                writer.write_all(b"],\"columns\":{")?;
+                for (j, (name, column)) in self.columns().iter().enumerate() {
+                    if j > 0 {
+                        writer.write_all(b",")?;
+                    }
+                    write_string(&mut writer, name)?;
+                    writer.write_all(b":[")?;
+                    for i in 0..column.len() {
+                        if i > 0 {
+                            writer.write_all(b",")?;
+                        }
+                        write_cell(&mut writer, column, i)?;
+                    }
+                    writer.write_all(b"]")?;
+                }
+                writer.write_all(b"}}")?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a frame written in the given orientation. `null` becomes a
+    /// missing value; nested arrays and objects are rejected. With `Records`,
+    /// columns are ordered by first appearance and rows are labeled `0..n`.
+    /// The whole document is buffered in memory before it is parsed.
+    pub fn from_json<R: Read>(mut reader: R, orient: JsonOrient) -> Result<DataFrame, RudasError> {
+        let mut src = String::new();
+        reader.read_to_string(&mut src)?;
+        let doc = parse(&src)?;
+
+        let mut df = DataFrame::new();
+        match (orient, doc) {
+            (JsonOrient::Records, Json::Array(rows)) => {
+                let mut names: Vec<&str> = Vec::new();
+                for row in &rows {
+                    match row {
+                        Json::Object(members) => {
+                            for (name, _) in members {
+                                if !names.contains(&name.as_str()) {
+                                    names.push(name);
+                                }
+                            }
+                        }
+                        _ => return Err(invalid(orient)),
+                    }
+                }
+                for name in names {
+                    let cells: Vec<&Json> = rows
+                        .iter()
+                        .map(|row| match row {
+                            Json::Object(members) => members
+                                .iter()
+                                .find(|(n, _)| n == name)
+                                .map_or(&Json::Null, |(_, v)| v),
+                            _ => &Json::Null,
+                        })
+                        .collect();
+                    df.add_column(name, json_column(name, &cells)?)?;
+                }
+                if df.width() == 0 {
+                    df.set_label((0..rows.len()).map(|i| i.to_string()).collect())?;
+                }
+            }
+            (JsonOrient::Split, Json::Object(members)) => {
+                let mut labels = None;
+                for (key, value) in &members {
+                    match (key.as_str(), value) {
+                        ("labels", Json::Array(items)) => {
+                            labels = Some(
+                                items
+                                    .iter()
+                                    .map(|item| match item {
+                                        Json::Str(s) => Ok(s.clone()),
+                                        Json::Int(x) => Ok(x.to_string()),
+                                        _ => Err(invalid(orient)),
+                                    })
+                                    .collect::<Result<Vec<_>, _>>()?,
+                            );
+                        }
+                        ("columns", Json::Object(columns)) => {
+                            for (name, cells) in columns {
+                                match cells {
+                                    Json::Array(cells) => {
+                                        let cells: Vec<&Json> = cells.iter().collect();
+                                        df.add_column(name, json_column(name, &cells)?)?;
+                                    }
+                                    _ => return Err(invalid(orient)),
+                                }
+                            }
+                        }
+                        _ => return Err(invalid(orient)),
+                    }
+                }
+                if let Some(labels) = labels {
+                    df.set_label(labels)?;
+                }
+            }
+            _ => return Err(invalid(orient)),
+        }
+        Ok(df)
+    }
+}
+
+impl<T: Field + Clone, U: PartialEq + Clone + Display> Series<T, U> {
+    /// Writes the series as a frame with a single `value` column.
+    pub fn to_json<W: Write>(&self, writer: W, orient: JsonOrient) -> Result<(), RudasError> {
+        self.to_frame("value").to_json(writer, orient)
+    }
+}
+
+impl<T: Field + Clone> Series<T, String> {
+    /// Reads a series written by `Series::to_json`.
+    pub fn from_json<R: Read>(
+        reader: R,
+        orient: JsonOrient,
+    ) -> Result<Series<T, String>, RudasError> {
+        DataFrame::from_json(reader, orient)?.series("value")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mixed_frame() -> DataFrame {
+        let mut df = DataFrame::from_columns(vec![
+            ("s", Column::Str(vec![Some("a\"b\\\n\u{1}é".into()), None])),
+            ("f", Column::F64(vec![Some(1.0), None])),
+            ("i", Column::from(vec![1i64, -2])),
+            ("b", Column::Bool(vec![Some(true), None])),
+        ])
+        .unwrap();
+        df.set_label(vec!["x".into(), "y".into()]).unwrap();
+        df
+    }
+
+    #[test]
+    fn frames_round_trip_in_both_orients() {
+        let df = mixed_frame();
+        for orient in [JsonOrient::Records, JsonOrient::Split] {
+            let mut out = Vec::new();
+            df.to_json(&mut out, orient).unwrap();
+            let back = DataFrame::from_json(&out[..], orient).unwrap();
+            match orient {
+                JsonOrient::Split => assert_eq!(back, df),
+                JsonOrient::Records => assert_eq!(back.columns(), df.columns()),
+            }
+        }
+    }
+
+    #[test]
+    fn reads_unicode_and_missing_fields() {
+        let df = DataFrame::from_json(
+            r#" [ {"a": 1, "b": "é😀"}, {"a": 2.5} ] "#.as_bytes(),
+            JsonOrient::Records,
+        )
+        .unwrap();
+        assert_eq!(df.column("a").unwrap(), &Column::from(vec![1.0, 2.5]));
+        assert_eq!(
+            df.column("b").unwrap(),
+            &Column::Str(vec![Some("é😀".into()), None])
+        );
+        let df = DataFrame::from_json(
+            r#"{"labels":["a"],"columns":{"x":["😀é\t"]}}"#.as_bytes(),
+            JsonOrient::Split,
+        )
+        .unwrap();
+        assert_eq!(
+            df.column("x").unwrap(),
+            &Column::Str(vec![Some("😀é\t".into())])
+        );
+    }
+
+    #[test]
+    fn rejects_nested_and_mixed_values() {
+        assert!(DataFrame::from_json(r#"[{"a": [1]}]"#.as_bytes(), JsonOrient::Records).is_err());
+        assert!(DataFrame::from_json(
+            r#"{"labels":["a","b"],"columns":{"x":[1,"a"]}}"#.as_bytes(),
+            JsonOrient::Split
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn series_round_trip() {
+        let s = Series::from(&[1i64, 2]);
+        let mut out = Vec::new();
+        s.to_json(&mut out, JsonOrient::Split).unwrap();
+        let back: Series<i64, String> = Series::from_json(&out[..], JsonOrient::Split).unwrap();
+        assert_eq!(back.data, vec![1, 2]);
+        assert_eq!(back.label, vec!["0", "1"]);
+    }
+
+    #[test]
+    fn rejects_deeply_nested_input() {
+        let deep = "[".repeat(200_000);
+        assert!(DataFrame::from_json(deep.as_bytes(), JsonOrient::Records).is_err());
+        let deep = format!("{}{}", "[".repeat(4), "]".repeat(4));
+        assert!(matches!(
+            parse(&deep),
+            Err(RudasError::Parse { message, .. }) if message == "nesting too deep"
+        ));
+        assert!(parse("[[[]]]").is_ok());
+    }
+
+    #[test]
+    fn rejects_bad_surrogates_and_numbers() {
+        assert_eq!(parse(r#""\ud83d\ude00""#).unwrap(), Json::Str("😀".into()));
+        assert!(parse(r#""\ud800\u0041""#).is_err());
+        assert!(parse(r#""\ud800""#).is_err());
+        assert!(parse(r#""\udc00""#).is_err());
+        for bad in [
+            "+1", "01", "-01", ".5", "1.", "-", "1e", "1e+", "--1", "1.5.2",
+        ] {
+            assert!(parse(bad).is_err(), "{}", bad);
+        }
+        assert_eq!(parse("-0").unwrap(), Json::Int(0));
+        assert_eq!(parse("0.5").unwrap(), Json::Float(0.5));
+        assert_eq!(parse("-1.5E+2").unwrap(), Json::Float(-150.0));
+        assert_eq!(parse("1e2").unwrap(), Json::Float(100.0));
+        assert_eq!(
+            parse("[10,-3]").unwrap(),
+            Json::Array(vec![Json::Int(10), Json::Int(-3)])
+        );
+    }
+}
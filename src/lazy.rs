@@ -0,0 +1,205 @@
+use std::marker::PhantomData;
+
+use crate::{
+    data::{clip_of, Series},
+    num::Numeric,
+};
+
+/// A pipeline of element-wise operations over a borrowed `Series`.
+///
+/// The steps are composed into a single function and nothing is evaluated
+/// until a terminal method such as `collect` or `sum` runs it in one pass
+/// over the data. `f` yields `None` for elements that a `filter` drops.
+pub struct LazySeries<'a, T: Clone, U: PartialEq + Clone, V, F> {
+    series: &'a Series<T, U>,
+    f: F,
+    value: PhantomData<fn() -> V>,
+}
+
+impl<T: Clone, U: PartialEq + Clone> Series<T, U> {
+    pub fn lazy(&self) -> LazySeries<'_, T, U, T, impl Fn(&T) -> Option<T>> {
+        LazySeries {
+            series: self,
+            f: |x: &T| Some(x.clone()),
+            value: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, U, V, F> LazySeries<'a, T, U, V, F>
+where
+    T: Clone,
+    U: PartialEq + Clone,
+    F: Fn(&T) -> Option<V>,
+{
+    fn then<R>(
+        self,
+        g: impl Fn(V) -> Option<R>,
+    ) -> LazySeries<'a, T, U, R, impl Fn(&T) -> Option<R>> {
+        let f = self.f;
+        LazySeries {
+            series: self.series,
+            f: move |x: &T| f(x).and_then(&g),
+            value: PhantomData,
+        }
+    }
+
+    pub fn map<R, G: Fn(&V) -> R>(self, g: G) -> LazySeries<'a, T, U, R, impl Fn(&T) -> Option<R>> {
+        self.then(move |v| Some(g(&v)))
+    }
+
+    /// Keeps the elements for which `g` returns true.
+    pub fn filter<G: Fn(&V) -> bool>(
+        self,
+        g: G,
+    ) -> LazySeries<'a, T, U, V, impl Fn(&T) -> Option<V>> {
+        self.then(move |v| g(&v).then_some(v))
+    }
+
+    /// Runs the pipeline, keeping the label of every element that passes.
+    pub fn collect(self) -> Series<V, U>
+    where
+        V: Clone,
+    {
+        let mut out = self.series.with_parts(Vec::new(), Vec::new());
+        for (x, l) in self.series.data.iter().zip(&self.series.label) {
+            if let Some(v) = (self.f)(x) {
+                out.data.push(v);
+                out.label.push(l.clone());
+            }
+        }
+        out
+    }
+
+    /// Runs the pipeline and counts the elements that pass.
+    pub fn count(self) -> usize {
+        self.values().count()
+    }
+
+    fn values(&self) -> impl Iterator<Item = V> + '_ {
+        self.series.data.iter().filter_map(&self.f)
+    }
+}
+
+impl<'a, T, U, V, F> LazySeries<'a, T, U, V, F>
+where
+    T: Clone,
+    U: PartialEq + Clone,
+    V: Numeric,
+    F: Fn(&T) -> Option<V>,
+{
+    /// Limits the values to `[lower, upper]`.
+    pub fn clip(self, lower: V, upper: V) -> LazySeries<'a, T, U, V, impl Fn(&T) -> Option<V>> {
+        self.then(move |v| Some(clip_of(v, lower, upper)))
+    }
+
+    pub fn add_scalar(self, rhs: V) -> LazySeries<'a, T, U, V, impl Fn(&T) -> Option<V>> {
+        self.then(move |v| Some(v + rhs))
+    }
+
+    pub fn sub_scalar(self, rhs: V) -> LazySeries<'a, T, U, V, impl Fn(&T) -> Option<V>> {
+        self.then(move |v| Some(v - rhs))
+    }
+
+    pub fn mul_scalar(self, rhs: V) -> LazySeries<'a, T, U, V, impl Fn(&T) -> Option<V>> {
+        self.then(move |v| Some(v * rhs))
+    }
+
+    pub fn div_scalar(self, rhs: V) -> LazySeries<'a, T, U, V, impl Fn(&T) -> Option<V>> {
+        self.then(move |v| Some(v / rhs))
+    }
+
+    /// Runs the pipeline and adds up the values that pass.
+    pub fn sum(self) -> V {
+        self.values().fold(V::zero(), |acc, x| acc + x)
+    }
+
+    /// Runs the pipeline and averages the values that pass. Returns `NAN`
+    /// when none do.
+    pub fn mean(self) -> f64 {
+        let (sum, n) = self
+            .values()
+            .fold((0.0, 0usize), |(sum, n), x| (sum + x.to_f64(), n + 1));
+        if n == 0 {
+            f64::NAN
+        } else {
+            sum / n as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::Series;
+
+    /// Deterministic pseudo-random integers in `-500..500`.
+    fn generator(mut seed: u64) -> impl FnMut() -> i64 {
+        move || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as i64 % 1000 - 500
+        }
+    }
+
+    #[test]
+    fn integer_pipelines_match_eager() {
+        let mut next = generator(42);
+        for _ in 0..50 {
+            let values: Vec<i64> = (0..200).map(|_| next()).collect();
+            let (a, b, t) = (next() % 7 + 1, next().abs(), next());
+            let s = Series::from(&values);
+            let eager = s
+                .map(|x| x * a)
+                .filter_by(|x| *x > t)
+                .clip(-b, b)
+                .map(|x| x - 3);
+            let lazy = || {
+                s.lazy()
+                    .mul_scalar(a)
+                    .filter(|x| *x > t)
+                    .clip(-b, b)
+                    .sub_scalar(3)
+            };
+            let collected = lazy().collect();
+            assert_eq!(collected.data, eager.data);
+            assert_eq!(collected.label, eager.label);
+            assert_eq!(lazy().sum(), eager.sum());
+            assert_eq!(lazy().count(), eager.len());
+        }
+    }
+
+    #[test]
+    fn float_pipelines_match_eager() {
+        let mut next = generator(7);
+        for _ in 0..50 {
+            let values: Vec<f64> = (0..200).map(|_| next() as f64 / 10.0).collect();
+            let (a, t) = (next() as f64 / 100.0, next() as f64 / 10.0);
+            let s = Series::from(&values);
+            let eager = s
+                .map(|x| x + a)
+                .filter_by(|x| *x < t)
+                .map(|x| x / 4.0)
+                .clip(-10.0, 10.0);
+            let lazy = || {
+                s.lazy()
+                    .add_scalar(a)
+                    .filter(move |x| *x < t)
+                    .div_scalar(4.0)
+                    .clip(-10.0, 10.0)
+            };
+            assert_eq!(lazy().collect().data, eager.data);
+            let (m, e) = (lazy().mean(), eager.mean());
+            assert!(m == e || (m.is_nan() && e.is_nan()));
+        }
+    }
+
+    #[test]
+    fn empty_pipelines() {
+        let s = Series::from(&[1, 2, 3]);
+        assert_eq!(s.lazy().filter(|x| *x > 5).count(), 0);
+        assert_eq!(s.lazy().filter(|x| *x > 5).sum(), 0);
+        assert!(s.lazy().filter(|x| *x > 5).mean().is_nan());
+        assert!(s.lazy().filter(|x| *x > 5).collect().is_empty());
+    }
+}
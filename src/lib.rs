@@ -1 +1,28 @@
+#[cfg(feature = "arrow")]
+mod arrow_impl;
+#[cfg(feature = "chrono")]
+mod chrono_impl;
+pub mod csv;
 pub mod data;
+pub mod error;
+pub mod frame;
+pub mod groupby;
+pub mod interval;
+pub mod json;
+pub mod lazy;
+#[cfg(feature = "ndarray")]
+mod ndarray_impl;
+pub mod num;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "rand")]
+mod rand_impl;
+pub mod record;
+#[cfg(feature = "serde")]
+mod serde_impl;
+pub mod snapshot;
+pub mod stream;
+pub mod table;
+pub mod testing;
@@ -0,0 +1,112 @@
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+
+use crate::{
+    data::Series,
+    error::RudasError,
+    frame::{Column, DataFrame},
+};
+
+impl<T: Clone, U: PartialEq + Clone> Series<T, U> {
+    pub fn to_ndarray(&self) -> Array1<T> {
+        Array1::from(self.data.clone())
+    }
+
+    /// Borrows the data as an array view without copying.
+    pub fn as_ndarray(&self) -> ArrayView1<'_, T> {
+        ArrayView1::from(&self.data[..])
+    }
+
+    pub fn from_ndarray(array: Array1<T>, label: &[U]) -> Result<Series<T, U>, RudasError> {
+        if array.len() != label.len() {
+            return Err(RudasError::LengthMismatch {
+                expected: array.len(),
+                found: label.len(),
+            });
+        }
+        Ok(Series::from_parts(array.to_vec(), label.to_vec()))
+    }
+}
+
+impl DataFrame {
+    /// Stacks the numeric columns into a `rows x columns` matrix, with
+    /// missing values as `NAN`. Non-numeric columns are an error unless they
+    /// are listed in `exclude`.
+    pub fn to_ndarray2(&self, exclude: &[&str]) -> Result<Array2<f64>, RudasError> {
+        let mut columns = Vec::new();
+        for (name, column) in self.columns() {
+            if exclude.contains(&name.as_str()) {
+                continue;
+            }
+            if !matches!(column, Column::I64(_) | Column::F64(_)) {
+                return Err(RudasError::TypeMismatch {
+                    column: name.clone(),
+                    expected: "numeric",
+                });
+            }
+            columns.push(column);
+        }
+        Ok(Array2::from_shape_fn(
+            (self.len(), columns.len()),
+            |(i, j)| columns[j].f64_at(i).unwrap_or(f64::NAN),
+        ))
+    }
+
+    /// Builds a frame of `f64` columns from a `rows x columns` matrix.
+    pub fn from_ndarray2(
+        array: ArrayView2<'_, f64>,
+        names: &[&str],
+    ) -> Result<DataFrame, RudasError> {
+        if array.ncols() != names.len() {
+            return Err(RudasError::LengthMismatch {
+                expected: array.ncols(),
+                found: names.len(),
+            });
+        }
+        DataFrame::from_columns(
+            names
+                .iter()
+                .zip(array.columns())
+                .map(|(name, values)| (*name, Column::from(values.to_vec())))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+
+    #[test]
+    fn series_converts_to_and_from_arrays() {
+        let s = Series::from(&[1.0, 2.0]);
+        assert_eq!(s.to_ndarray(), array![1.0, 2.0]);
+        assert_eq!(s.as_ndarray().sum(), 3.0);
+        let back = Series::from_ndarray(array![1, 2], &["a", "b"]).unwrap();
+        assert_eq!(back.as_parts(), (&[1, 2][..], &["a", "b"][..]));
+        assert!(matches!(
+            Series::from_ndarray(array![1, 2], &["a"]),
+            Err(RudasError::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn frame_converts_numeric_columns_to_a_matrix() {
+        let df = DataFrame::from_columns(vec![
+            ("a", Column::from(vec![1i64, 2])),
+            ("b", Column::F64(vec![None, Some(2.5)])),
+            ("s", Column::from(vec!["x", "y"])),
+        ])
+        .unwrap();
+        assert!(df.to_ndarray2(&[]).is_err());
+        let m = df.to_ndarray2(&["s"]).unwrap();
+        assert_eq!(m.shape(), &[2, 2]);
+        assert_eq!(m[[1, 1]], 2.5);
+        assert!(m[[0, 1]].is_nan());
+
+        let df =
+            DataFrame::from_ndarray2(array![[1.0, 2.0], [3.0, 4.0]].view(), &["x", "y"]).unwrap();
+        assert_eq!(df.column("y").unwrap(), &Column::from(vec![2.0, 4.0]));
+    }
+}
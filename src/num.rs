@@ -0,0 +1,83 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+/// Primitive numbers a `Series` can compute statistics over.
+pub trait Numeric:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn to_f64(self) -> f64;
+    /// `None` on overflow. For floats, when finite operands give an
+    /// infinite result.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_numeric {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn zero() -> Self {
+                    0 as $t
+                }
+
+                fn one() -> Self {
+                    1 as $t
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_numeric_float {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn one() -> Self {
+                    1.0
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    finite(self, rhs, self + rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    finite(self, rhs, self * rhs)
+                }
+            }
+        )*
+    };
+}
+
+fn finite<T: Into<f64> + Copy>(a: T, b: T, result: T) -> Option<T> {
+    let overflow = a.into().is_finite() && b.into().is_finite() && result.into().is_infinite();
+    (!overflow).then_some(result)
+}
+
+impl_numeric!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_numeric_float!(f32, f64);
@@ -0,0 +1,182 @@
+use rayon::prelude::*;
+
+use crate::{data::Series, groupby::GroupBy, num::Numeric};
+
+/// Number of consecutive elements summed sequentially before the partial
+/// sums are combined.
+pub const BLOCK_SIZE: usize = 4096;
+
+/// Adds the values by recursively splitting them in halves.
+fn pairwise_sum<T: Numeric>(values: &[T]) -> T {
+    match values.len() {
+        0 => T::zero(),
+        1 => values[0],
+        n => pairwise_sum(&values[..n / 2]) + pairwise_sum(&values[n / 2..]),
+    }
+}
+
+fn pick<T: Numeric>(values: impl Iterator<Item = T>, keep: fn(&T, &T) -> bool) -> Option<T> {
+    values.reduce(|a, b| if keep(&b, &a) { b } else { a })
+}
+
+impl<T, U> Series<T, U>
+where
+    T: Clone + Sync,
+    U: PartialEq + Clone + Sync,
+{
+    /// Maps every value with `f` on the rayon thread pool. The output keeps
+    /// the input order.
+    pub fn par_map<R, F>(&self, f: F) -> Series<R, U>
+    where
+        R: Clone + Send,
+        F: Fn(&T) -> R + Sync,
+    {
+        self.with_data(self.data.par_iter().map(&f).collect())
+    }
+
+    /// Keeps the elements for which `f` returns true, evaluated on the rayon
+    /// thread pool. The output keeps the input order.
+    pub fn par_filter_by<F>(&self, f: F) -> Series<T, U>
+    where
+        T: Send,
+        U: Send,
+        F: Fn(&T) -> bool + Sync,
+    {
+        let (data, label) = self
+            .data
+            .par_iter()
+            .zip(self.label.par_iter())
+            .filter(|(v, _)| f(v))
+            .map(|(v, l)| (v.clone(), l.clone()))
+            .unzip();
+        self.with_parts(data, label)
+    }
+}
+
+/// Parallel reductions. The data is split into blocks of `BLOCK_SIZE`
+/// elements that are each reduced left to right, and the block results are
+/// combined pairwise. The order of operations depends only on the length, so
+/// float results are the same for every thread count.
+impl<T, U> Series<T, U>
+where
+    T: Numeric + Send + Sync,
+    U: PartialEq + Clone + Sync,
+{
+    pub fn par_sum(&self) -> T {
+        let blocks: Vec<T> = self
+            .data
+            .par_chunks(BLOCK_SIZE)
+            .map(|block| block.iter().fold(T::zero(), |acc, &x| acc + x))
+            .collect();
+        pairwise_sum(&blocks)
+    }
+
+    /// Returns `NAN` for an empty series.
+    pub fn par_mean(&self) -> f64 {
+        if self.is_empty() {
+            return f64::NAN;
+        }
+        let blocks: Vec<f64> = self
+            .data
+            .par_chunks(BLOCK_SIZE)
+            .map(|block| block.iter().map(|x| x.to_f64()).sum())
+            .collect();
+        pairwise_sum(&blocks) / self.len() as f64
+    }
+
+    pub fn par_min(&self) -> Option<T> {
+        let blocks: Vec<T> = self
+            .data
+            .par_chunks(BLOCK_SIZE)
+            .filter_map(|block| pick(block.iter().copied(), |b, a| b < a))
+            .collect();
+        pick(blocks.into_iter(), |b, a| b < a)
+    }
+
+    pub fn par_max(&self) -> Option<T> {
+        let blocks: Vec<T> = self
+            .data
+            .par_chunks(BLOCK_SIZE)
+            .filter_map(|block| pick(block.iter().copied(), |b, a| b > a))
+            .collect();
+        pick(blocks.into_iter(), |b, a| b > a)
+    }
+}
+
+impl<T, U, K> GroupBy<'_, T, U, K>
+where
+    T: Clone + Sync,
+    U: PartialEq + Clone + Sync,
+    K: PartialEq + Clone + Sync,
+{
+    /// Reduces every group with `f` on the rayon thread pool, one task per
+    /// group. The result is labeled by key in the order of `agg`.
+    pub fn par_agg<R, F>(&self, f: F) -> Series<R, K>
+    where
+        R: Clone + Send,
+        F: Fn(&Series<T, U>) -> R + Sync,
+    {
+        let data = self
+            .indices()
+            .par_iter()
+            .map(|positions| f(&self.subset(positions)))
+            .collect();
+        self.by_key(data)
+    }
+}
+
+impl<T, U, K> GroupBy<'_, T, U, K>
+where
+    T: Numeric + Send + Sync,
+    U: PartialEq + Clone + Sync,
+    K: PartialEq + Clone + Sync,
+{
+    /// Sums every group sequentially, in parallel across groups, so the
+    /// result equals `sum`.
+    pub fn par_sum(&self) -> Series<T, K> {
+        self.par_agg(|group| group.sum())
+    }
+
+    pub fn par_mean(&self) -> Series<f64, K> {
+        self.par_agg(|group| group.mean())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_reductions_match_sequential_ones() {
+        let values: Vec<f64> = (0..3 * BLOCK_SIZE + 7).map(|i| (i as f64).sin()).collect();
+        let s = Series::from(&values);
+        assert!((s.par_sum() - s.sum()).abs() < 1e-9);
+        assert_eq!(s.par_mean(), s.par_sum() / s.len() as f64);
+        assert_eq!(s.par_min(), values.iter().copied().reduce(f64::min));
+        assert_eq!(s.par_max(), values.iter().copied().reduce(f64::max));
+        assert_eq!(s.par_map(|v| v * 2.0).data, s.map(|v| v * 2.0).data);
+    }
+
+    #[test]
+    fn groupby_par_agg_matches_agg() {
+        let s = Series::from(&(0..1000).collect::<Vec<i64>>());
+        let keys: Vec<i64> = (0..1000).map(|i| i * 7 % 13).collect();
+        let g = s.groupby(&keys).unwrap();
+        assert_eq!(g.par_sum().data, g.sum().data);
+        assert_eq!(g.par_sum().label, g.keys());
+        assert_eq!(g.par_mean().data, g.mean().data);
+        assert_eq!(g.par_agg(|group| group.len()).data, g.size().data);
+    }
+
+    #[test]
+    fn par_filter_keeps_order_and_labels() {
+        let values: Vec<f64> = (0..100_000).map(|i| (i as f64).sin()).collect();
+        let s = Series::from(&values);
+        let kept = s.par_filter_by(|x| *x > 0.5);
+        let expected = s.filter_by(|x| *x > 0.5);
+        assert_eq!(kept.as_parts(), expected.as_parts());
+        assert_eq!(s.par_sum(), s.par_sum());
+        assert_eq!(Series::<i32>::from(&[]).par_min(), None);
+        assert!(Series::<f64>::from(&[]).par_mean().is_nan());
+    }
+}
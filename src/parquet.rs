@@ -0,0 +1,364 @@
+use std::{fs::File, path::Path, sync::Arc};
+
+use ::parquet::{
+    basic::{LogicalType, Repetition, Type as PhysicalType},
+    column::reader::{ColumnReader, ColumnReaderImpl},
+    data_type::{BoolType, ByteArray, ByteArrayType, DataType, DoubleType, Int64Type},
+    file::{
+        reader::{FileReader, SerializedFileReader},
+        writer::SerializedFileWriter,
+    },
+    schema::types::Type,
+};
+
+pub use crate::frame::LABEL_COLUMN;
+use crate::{
+    error::RudasError,
+    frame::{check_label_column, labels_from_column, Column, DataFrame},
+};
+
+fn field_type(
+    name: &str,
+    physical: PhysicalType,
+    repetition: Repetition,
+) -> Result<Arc<Type>, RudasError> {
+    let logical = (physical == PhysicalType::BYTE_ARRAY).then_some(LogicalType::String);
+    Ok(Arc::new(
+        Type::primitive_type_builder(name, physical)
+            .with_repetition(repetition)
+            .with_logical_type(logical)
+            .build()?,
+    ))
+}
+
+/// Splits cells into the present values and their definition levels.
+fn split_nulls<T, V>(cells: &[Option<T>], f: impl Fn(&T) -> V) -> (Vec<V>, Vec<i16>) {
+    let values = cells.iter().flatten().map(f).collect();
+    let levels = cells.iter().map(|c| c.is_some() as i16).collect();
+    (values, levels)
+}
+
+impl DataFrame {
+    /// Writes the frame as a single row group. `i64`, `f64`, `bool` and `str`
+    /// columns map to optional `INT64`, `DOUBLE`, `BOOLEAN` and UTF-8
+    /// `BYTE_ARRAY` columns, and the row labels are stored first as
+    /// `LABEL_COLUMN`. Fails when a column already has that name.
+    pub fn to_parquet<P: AsRef<Path>>(&self, path: P) -> Result<(), RudasError> {
+        check_label_column(self)?;
+        let mut fields = vec![field_type(
+            LABEL_COLUMN,
+            PhysicalType::BYTE_ARRAY,
+            Repetition::REQUIRED,
+        )?];
+        for (name, column) in self.columns() {
+            let physical = match column {
+                Column::I64(_) => PhysicalType::INT64,
+                Column::F64(_) => PhysicalType::DOUBLE,
+                Column::Bool(_) => PhysicalType::BOOLEAN,
+                Column::Str(_) => PhysicalType::BYTE_ARRAY,
+            };
+            fields.push(field_type(name, physical, Repetition::OPTIONAL)?);
+        }
+        let schema = Arc::new(
+            Type::group_type_builder("schema")
+                .with_fields(fields)
+                .build()?,
+        );
+
+        let mut writer =
+            SerializedFileWriter::new(File::create(path)?, schema, Default::default())?;
+        let mut row_group = writer.next_row_group()?;
+
+        let no_column = |name: &str| {
+            RudasError::InvalidFormat(format!("parquet writer has no column for {}", name))
+        };
+        let mut label = row_group
+            .next_column()?
+            .ok_or_else(|| no_column(LABEL_COLUMN))?;
+        let labels: Vec<ByteArray> = self
+            .label()
+            .iter()
+            .map(|l| ByteArray::from(l.as_str()))
+            .collect();
+        label
+            .typed::<ByteArrayType>()
+            .write_batch(&labels, None, None)?;
+        label.close()?;
+
+        for (name, column) in self.columns() {
+            let mut out = row_group.next_column()?.ok_or_else(|| no_column(name))?;
+            match column {
+                Column::I64(v) => {
+                    let (values, levels) = split_nulls(v, |x| *x);
+                    out.typed::<Int64Type>()
+                        .write_batch(&values, Some(&levels), None)?;
+                }
+                Column::F64(v) => {
+                    let (values, levels) = split_nulls(v, |x| *x);
+                    out.typed::<DoubleType>()
+                        .write_batch(&values, Some(&levels), None)?;
+                }
+                Column::Bool(v) => {
+                    let (values, levels) = split_nulls(v, |x| *x);
+                    out.typed::<BoolType>()
+                        .write_batch(&values, Some(&levels), None)?;
+                }
+                Column::Str(v) => {
+                    let (values, levels) = split_nulls(v, |x| ByteArray::from(x.as_str()));
+                    out.typed::<ByteArrayType>()
+                        .write_batch(&values, Some(&levels), None)?;
+                }
+            }
+            out.close()?;
+        }
+        row_group.close()?;
+        writer.close()?;
+        Ok(())
+    }
+}
+
+/// Reads all `rows` cells of one column chunk with the typed reader, using
+/// the definition levels to place the missing values when `nullable`.
+fn read_chunk<T: DataType>(
+    reader: &mut ColumnReaderImpl<T>,
+    rows: usize,
+    nullable: bool,
+) -> Result<Vec<Option<T::T>>, RudasError> {
+    let mut values = Vec::with_capacity(rows);
+    let mut levels = Vec::with_capacity(rows);
+    let mut read = 0;
+    while read < rows {
+        let (records, _, _) = reader.read_records(
+            rows - read,
+            nullable.then_some(&mut levels),
+            None,
+            &mut values,
+        )?;
+        if records == 0 {
+            return Err(RudasError::InvalidFormat(format!(
+                "column chunk ends after {} of {} rows",
+                read, rows
+            )));
+        }
+        read += records;
+    }
+    if !nullable {
+        return Ok(values.into_iter().map(Some).collect());
+    }
+    let mut values = values.into_iter();
+    Ok(levels
+        .iter()
+        .map(|&level| if level > 0 { values.next() } else { None })
+        .collect())
+}
+
+/// Reads a Parquet file of flat primitive columns one column chunk at a time.
+/// 32- and 64-bit integers and floats become `i64` and `f64` columns. A
+/// `LABEL_COLUMN` column, when present, becomes the row labels; it must be a
+/// single string column without nulls.
+pub fn read_parquet<P: AsRef<Path>>(path: P) -> Result<DataFrame, RudasError> {
+    let reader = SerializedFileReader::new(File::open(path)?)?;
+    let descriptors = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .to_vec();
+
+    let mut columns = Vec::with_capacity(descriptors.len());
+    for descriptor in &descriptors {
+        if descriptor.max_rep_level() > 0 {
+            return Err(RudasError::InvalidArgument(format!(
+                "repeated parquet column {} is not supported",
+                descriptor.name()
+            )));
+        }
+        columns.push(match descriptor.physical_type() {
+            PhysicalType::INT32 | PhysicalType::INT64 => Column::I64(Vec::new()),
+            PhysicalType::FLOAT | PhysicalType::DOUBLE => Column::F64(Vec::new()),
+            PhysicalType::BOOLEAN => Column::Bool(Vec::new()),
+            PhysicalType::BYTE_ARRAY => Column::Str(Vec::new()),
+            other => {
+                return Err(RudasError::InvalidArgument(format!(
+                    "unsupported parquet type {} in column {}",
+                    other,
+                    descriptor.name()
+                )))
+            }
+        });
+    }
+
+    for i in 0..reader.num_row_groups() {
+        let row_group = reader.get_row_group(i)?;
+        let rows = row_group.metadata().num_rows() as usize;
+        for (j, (descriptor, column)) in descriptors.iter().zip(columns.iter_mut()).enumerate() {
+            let nullable = descriptor.max_def_level() > 0;
+            match (column, row_group.get_column_reader(j)?) {
+                (Column::I64(v), ColumnReader::Int32ColumnReader(mut r)) => v.extend(
+                    read_chunk(&mut r, rows, nullable)?
+                        .into_iter()
+                        .map(|x| x.map(i64::from)),
+                ),
+                (Column::I64(v), ColumnReader::Int64ColumnReader(mut r)) => {
+                    v.extend(read_chunk(&mut r, rows, nullable)?)
+                }
+                (Column::F64(v), ColumnReader::FloatColumnReader(mut r)) => v.extend(
+                    read_chunk(&mut r, rows, nullable)?
+                        .into_iter()
+                        .map(|x| x.map(f64::from)),
+                ),
+                (Column::F64(v), ColumnReader::DoubleColumnReader(mut r)) => {
+                    v.extend(read_chunk(&mut r, rows, nullable)?)
+                }
+                (Column::Bool(v), ColumnReader::BoolColumnReader(mut r)) => {
+                    v.extend(read_chunk(&mut r, rows, nullable)?)
+                }
+                (Column::Str(v), ColumnReader::ByteArrayColumnReader(mut r)) => {
+                    for cell in read_chunk(&mut r, rows, nullable)? {
+                        v.push(match cell {
+                            Some(bytes) => Some(
+                                bytes
+                                    .as_utf8()
+                                    .map_err(|_| RudasError::TypeMismatch {
+                                        column: descriptor.name().to_string(),
+                                        expected: "UTF-8 text",
+                                    })?
+                                    .to_string(),
+                            ),
+                            None => None,
+                        });
+                    }
+                }
+                _ => {
+                    return Err(RudasError::TypeMismatch {
+                        column: descriptor.name().to_string(),
+                        expected: "a flat primitive value",
+                    })
+                }
+            }
+        }
+    }
+
+    let mut df = DataFrame::new();
+    let mut label = None;
+    for (descriptor, column) in descriptors.iter().zip(columns) {
+        if descriptor.name() != LABEL_COLUMN {
+            df.add_column(descriptor.name(), column)?;
+        } else if label.is_some() {
+            return Err(RudasError::DuplicateColumn(LABEL_COLUMN.to_string()));
+        } else {
+            label = Some(labels_from_column(column)?);
+        }
+    }
+    if let Some(label) = label {
+        df.set_label(label)?;
+    }
+    Ok(df)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("rudas_{}_{}.parquet", name, std::process::id()))
+    }
+
+    #[test]
+    fn round_trips_columns_with_nulls() {
+        let mut df = DataFrame::from_columns(vec![
+            (
+                "s",
+                Column::Str(vec![Some("a,b".into()), None, Some("".into())]),
+            ),
+            ("f", Column::F64(vec![Some(1.0), None, Some(f64::INFINITY)])),
+            ("i", Column::I64(vec![None, Some(2), Some(3)])),
+            ("b", Column::Bool(vec![Some(true), None, Some(false)])),
+            ("n", Column::Str(vec![None, None, None])),
+        ])
+        .unwrap();
+        df.set_label(vec!["x".into(), "y".into(), "z".into()])
+            .unwrap();
+        let path = temp_path("nulls");
+        df.to_parquet(&path).unwrap();
+        let back = read_parquet(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(back.unwrap(), df);
+    }
+
+    #[test]
+    fn round_trips_an_empty_frame() {
+        let df = DataFrame::from_columns(vec![("i", Column::I64(Vec::new()))]).unwrap();
+        let path = temp_path("empty");
+        df.to_parquet(&path).unwrap();
+        let back = read_parquet(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(back.unwrap(), df);
+    }
+
+    #[test]
+    fn round_trips_a_frame_spanning_many_pages() {
+        let n = 200_000;
+        let mut df = DataFrame::from_columns(vec![
+            (
+                "i",
+                Column::I64((0..n).map(|i| (i % 7 != 0).then_some(i)).collect()),
+            ),
+            (
+                "s",
+                Column::Str(
+                    (0..n)
+                        .map(|i| (i % 5 != 0).then(|| i.to_string()))
+                        .collect(),
+                ),
+            ),
+        ])
+        .unwrap();
+        df.set_label((0..n).map(|i| format!("r{}", i)).collect())
+            .unwrap();
+        let path = temp_path("pages");
+        df.to_parquet(&path).unwrap();
+        let back = read_parquet(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(back.unwrap(), df);
+    }
+
+    #[test]
+    fn reserves_the_label_column_name() {
+        let df = DataFrame::from_columns(vec![(LABEL_COLUMN, Column::from(vec![1i64]))]).unwrap();
+        let path = temp_path("reserved");
+        assert!(matches!(
+            df.to_parquet(&path),
+            Err(RudasError::InvalidArgument(_))
+        ));
+        assert!(!path.exists());
+
+        let schema = Arc::new(
+            Type::group_type_builder("schema")
+                .with_fields(vec![field_type(
+                    LABEL_COLUMN,
+                    PhysicalType::INT64,
+                    Repetition::REQUIRED,
+                )
+                .unwrap()])
+                .build()
+                .unwrap(),
+        );
+        let path = temp_path("numeric_label");
+        let mut writer =
+            SerializedFileWriter::new(File::create(&path).unwrap(), schema, Default::default())
+                .unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<Int64Type>()
+            .write_batch(&[1, 2], None, None)
+            .unwrap();
+        column.close().unwrap();
+        row_group.close().unwrap();
+        writer.close().unwrap();
+        let back = read_parquet(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(back, Err(RudasError::TypeMismatch { .. })));
+    }
+}
@@ -0,0 +1,76 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{data::Series, error::RudasError};
+
+/// The train and test parts of `Series::train_test_split`.
+type Split<T, U> = (Series<T, U>, Series<T, U>);
+
+impl<T: Clone, U: PartialEq + Clone> Series<T, U> {
+    /// Randomly splits the series into a train and a test part, the latter
+    /// holding `test_frac` of the elements rounded to the nearest count.
+    /// Both parts keep the original order. The same `seed` always gives the
+    /// same split; without one the generator is seeded by the OS.
+    pub fn train_test_split(
+        &self,
+        test_frac: f64,
+        seed: Option<u64>,
+    ) -> Result<Split<T, U>, RudasError> {
+        if !(0.0..=1.0).contains(&test_frac) {
+            return Err(RudasError::InvalidArgument(format!(
+                "test fraction must be between 0 and 1, got {}",
+                test_frac
+            )));
+        }
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+        let mut positions: Vec<usize> = (0..self.len()).collect();
+        positions.shuffle(&mut rng);
+        let n_test = (self.len() as f64 * test_frac).round() as usize;
+        let (test, train) = positions.split_at_mut(n_test);
+        test.sort_unstable();
+        train.sort_unstable();
+        Ok((self.take(train)?, self.take(test)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::Series;
+
+    #[test]
+    fn split_is_a_disjoint_covering_partition() {
+        let s = Series::from_label(
+            &(0..100).collect::<Vec<i32>>(),
+            &(100..200).collect::<Vec<i32>>(),
+        );
+        let (train, test) = s.train_test_split(0.25, Some(7)).unwrap();
+        assert_eq!((train.len(), test.len()), (75, 25));
+        let mut all: Vec<i32> = train.data.iter().chain(&test.data).copied().collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..100).collect::<Vec<_>>());
+        for part in [&train, &test] {
+            assert!(part.data.windows(2).all(|w| w[0] < w[1]));
+            assert!(part
+                .data
+                .iter()
+                .zip(&part.label)
+                .all(|(d, l)| d + 100 == *l));
+        }
+    }
+
+    #[test]
+    fn split_is_reproducible_and_checks_the_fraction() {
+        let s = Series::from(&(0..50).collect::<Vec<i32>>());
+        let (_, a) = s.train_test_split(0.3, Some(11)).unwrap();
+        let (_, b) = s.train_test_split(0.3, Some(11)).unwrap();
+        assert_eq!(a.data, b.data);
+        assert!(s.train_test_split(1.5, None).is_err());
+        assert!(s.train_test_split(-0.1, None).is_err());
+        let (train, test) = s.train_test_split(0.0, None).unwrap();
+        assert_eq!((train.len(), test.len()), (50, 0));
+        let (train, test) = s.train_test_split(1.0, None).unwrap();
+        assert_eq!((train.len(), test.len()), (0, 50));
+    }
+}
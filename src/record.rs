@@ -0,0 +1,310 @@
+use crate::{
+    error::RudasError,
+    frame::{Column, DataFrame, Row},
+};
+
+/// A struct that can be stored as one row of a `DataFrame`, with one column
+/// per field in declaration order.
+///
+/// ```ignore
+/// impl Record for Trade {
+///     fn columns() -> Vec<(&'static str, Column)> {
+///         vec![("id", Column::of::<i64>()), ("note", Column::of::<Option<String>>())]
+///     }
+///
+///     fn write(&self, row: &mut RowWriter<'_>) {
+///         row.push(&self.id);
+///         row.push(&self.note);
+///     }
+///
+///     fn read(row: &Row<'_>) -> Result<Self, RudasError> {
+///         Ok(Trade { id: row.get("id")?, note: row.get("note")? })
+///     }
+/// }
+/// ```
+pub trait Record: Sized {
+    /// Column names paired with empty columns of the field types.
+    fn columns() -> Vec<(&'static str, Column)>;
+
+    /// Pushes every field, in the same order as `columns`.
+    fn write(&self, row: &mut RowWriter<'_>);
+
+    fn read(row: &Row<'_>) -> Result<Self, RudasError>;
+}
+
+/// A value that maps to a single `DataFrame` cell. `Option` fields map to
+/// missing values.
+pub trait Field: Sized {
+    fn empty_column() -> Column;
+
+    /// Appends the value to `column`. Returns false, leaving the column
+    /// unchanged, when the column holds another type.
+    fn push_to(&self, column: &mut Column) -> bool;
+
+    fn read(row: &Row<'_>, name: &str) -> Result<Self, RudasError>;
+}
+
+macro_rules! impl_field {
+    ($t:ty, $variant:ident, $name:expr) => {
+        impl Field for Option<$t> {
+            fn empty_column() -> Column {
+                Column::$variant(Vec::new())
+            }
+
+            #[allow(clippy::clone_on_copy)]
+            fn push_to(&self, column: &mut Column) -> bool {
+                match column {
+                    Column::$variant(v) => {
+                        v.push(self.clone());
+                        true
+                    }
+                    _ => false,
+                }
+            }
+
+            #[allow(clippy::clone_on_copy)]
+            fn read(row: &Row<'_>, name: &str) -> Result<Self, RudasError> {
+                match row.column(name)? {
+                    Column::$variant(v) => Ok(v[row.position()].clone()),
+                    _ => Err(RudasError::TypeMismatch {
+                        column: name.to_string(),
+                        expected: $name,
+                    }),
+                }
+            }
+        }
+
+        impl Field for $t {
+            fn empty_column() -> Column {
+                <Option<$t>>::empty_column()
+            }
+
+            fn push_to(&self, column: &mut Column) -> bool {
+                Some(self.clone()).push_to(column)
+            }
+
+            fn read(row: &Row<'_>, name: &str) -> Result<Self, RudasError> {
+                <Option<$t>>::read(row, name)?.ok_or_else(|| RudasError::MissingValue {
+                    column: name.to_string(),
+                    label: row.label().to_string(),
+                })
+            }
+        }
+    };
+}
+
+impl_field!(i64, I64, "i64");
+impl_field!(f64, F64, "f64");
+impl_field!(bool, Bool, "bool");
+impl_field!(String, Str, "str");
+
+/// Receives the fields of one `Record` and appends them to the columns.
+///
+/// Pushing more fields than there are columns, or a field whose type does
+/// not match its column, is recorded and makes `from_records` fail. Later
+/// pushes of the row are ignored.
+pub struct RowWriter<'a> {
+    names: &'a [&'static str],
+    columns: &'a mut [Column],
+    next: usize,
+    error: Option<RudasError>,
+}
+
+impl RowWriter<'_> {
+    pub fn push<F: Field>(&mut self, value: &F) {
+        if self.error.is_some() {
+            return;
+        }
+        self.error = match self.columns.get_mut(self.next) {
+            None => Some(RudasError::LengthMismatch {
+                expected: self.columns.len(),
+                found: self.next + 1,
+            }),
+            Some(column) => match value.push_to(column) {
+                true => None,
+                false => Some(RudasError::TypeMismatch {
+                    column: self.names[self.next].to_string(),
+                    expected: column.dtype().name(),
+                }),
+            },
+        };
+        self.next += 1;
+    }
+
+    /// Fails when the row missed or mistyped a field.
+    fn finish(self) -> Result<(), RudasError> {
+        match self.error {
+            Some(error) => Err(error),
+            None if self.next != self.columns.len() => Err(RudasError::LengthMismatch {
+                expected: self.columns.len(),
+                found: self.next,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Column {
+    pub fn of<F: Field>() -> Column {
+        F::empty_column()
+    }
+}
+
+impl DataFrame {
+    /// Builds a frame with one column per field of `R`. Rows are labeled
+    /// `0..n` in iteration order. Fails when `Record::write` pushes too few
+    /// or too many fields, or a field of the wrong type.
+    pub fn from_records<'a, R: Record + 'a>(
+        records: impl IntoIterator<Item = &'a R>,
+    ) -> Result<DataFrame, RudasError> {
+        let (names, mut columns): (Vec<_>, Vec<_>) = R::columns().into_iter().unzip();
+        for record in records {
+            let mut row = RowWriter {
+                names: &names,
+                columns: &mut columns,
+                next: 0,
+                error: None,
+            };
+            record.write(&mut row);
+            row.finish()?;
+        }
+        DataFrame::from_columns(names.into_iter().zip(columns).collect())
+    }
+
+    pub fn to_records<R: Record>(&self) -> Result<Vec<R>, RudasError> {
+        self.rows().map(|row| R::read(&row)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct Trade {
+        id: i64,
+        price: f64,
+        note: Option<String>,
+    }
+
+    impl Record for Trade {
+        fn columns() -> Vec<(&'static str, Column)> {
+            vec![
+                ("id", Column::of::<i64>()),
+                ("price", Column::of::<f64>()),
+                ("note", Column::of::<Option<String>>()),
+            ]
+        }
+
+        fn write(&self, row: &mut RowWriter<'_>) {
+            row.push(&self.id);
+            row.push(&self.price);
+            row.push(&self.note);
+        }
+
+        fn read(row: &Row<'_>) -> Result<Self, RudasError> {
+            Ok(Trade {
+                id: row.get("id")?,
+                price: row.get("price")?,
+                note: row.get("note")?,
+            })
+        }
+    }
+
+    /// Writes the first `fields` fields of a `Trade`, the second one as a
+    /// string when `mistyped`, and integers past the third.
+    struct Broken {
+        fields: usize,
+        mistyped: bool,
+    }
+
+    impl Record for Broken {
+        fn columns() -> Vec<(&'static str, Column)> {
+            Trade::columns()
+        }
+
+        fn write(&self, row: &mut RowWriter<'_>) {
+            for i in 0..self.fields {
+                match i {
+                    1 if self.mistyped => row.push(&"text".to_string()),
+                    1 => row.push(&1.0),
+                    2 => row.push(&None::<String>),
+                    _ => row.push(&(i as i64)),
+                }
+            }
+        }
+
+        fn read(_: &Row<'_>) -> Result<Self, RudasError> {
+            unreachable!()
+        }
+    }
+
+    fn trades() -> Vec<Trade> {
+        vec![
+            Trade {
+                id: 1,
+                price: 2.5,
+                note: None,
+            },
+            Trade {
+                id: 2,
+                price: 1.0,
+                note: Some("x".into()),
+            },
+        ]
+    }
+
+    #[test]
+    fn records_round_trip() {
+        let trades = trades();
+        let df = DataFrame::from_records(trades.iter()).unwrap();
+        assert_eq!(df.column_names(), vec!["id", "price", "note"]);
+        assert_eq!(
+            df.column("note").unwrap(),
+            &Column::Str(vec![None, Some("x".into())])
+        );
+        assert_eq!(df.to_records::<Trade>().unwrap(), trades);
+        let empty: Vec<Trade> = Vec::new();
+        assert_eq!(DataFrame::from_records(empty.iter()).unwrap().width(), 3);
+    }
+
+    #[test]
+    fn missing_required_field_fails_to_read() {
+        let df = DataFrame::from_columns(vec![
+            ("id", Column::I64(vec![None])),
+            ("price", Column::from(vec![1.0])),
+            ("note", Column::Str(vec![None])),
+        ])
+        .unwrap();
+        assert!(matches!(
+            df.to_records::<Trade>(),
+            Err(RudasError::MissingValue { .. })
+        ));
+    }
+
+    #[test]
+    fn badly_written_records_are_errors() {
+        let write =
+            |fields, mistyped| DataFrame::from_records([Broken { fields, mistyped }].iter());
+        assert!(matches!(
+            write(4, false),
+            Err(RudasError::LengthMismatch {
+                expected: 3,
+                found: 4
+            })
+        ));
+        assert!(matches!(
+            write(2, false),
+            Err(RudasError::LengthMismatch {
+                expected: 3,
+                found: 2
+            })
+        ));
+        match write(3, true) {
+            Err(RudasError::TypeMismatch { column, expected }) => {
+                assert_eq!((column.as_str(), expected), ("price", "f64"));
+            }
+            other => panic!("expected a type mismatch, got {:?}", other),
+        }
+    }
+}
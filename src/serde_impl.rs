@@ -0,0 +1,106 @@
+use serde::{de::Error, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::data::Series;
+
+/// A series is serialized as
+/// `{ "data": [...], "label": [...], "name": ..., "index_name": ... }`.
+impl<T, U> Serialize for Series<T, U>
+where
+    T: Clone + Serialize,
+    U: PartialEq + Clone + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Series", 4)?;
+        state.serialize_field("data", &self.data)?;
+        state.serialize_field("label", &self.label)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("index_name", &self.index_name)?;
+        state.end()
+    }
+}
+
+/// Fails when `data` and `label` do not have the same length. Missing names
+/// are read as `None`.
+impl<'de, T, U> Deserialize<'de> for Series<T, U>
+where
+    T: Clone + Deserialize<'de>,
+    U: PartialEq + Clone + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "Series")]
+        struct Raw<T, U> {
+            data: Vec<T>,
+            label: Vec<U>,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            index_name: Option<String>,
+        }
+
+        let raw = Raw::<T, U>::deserialize(deserializer)?;
+        if raw.data.len() != raw.label.len() {
+            return Err(D::Error::custom(format!(
+                "data has {} elements but label has {}",
+                raw.data.len(),
+                raw.label.len()
+            )));
+        }
+        Ok(Series {
+            name: raw.name,
+            index_name: raw.index_name,
+            ..Series::from_parts(raw.data, raw.label)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip_through_json() {
+        let s = Series::from_label(&[1, 2], &["a", "b"]).with_name("n");
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(
+            json,
+            r#"{"data":[1,2],"label":["a","b"],"name":"n","index_name":null}"#
+        );
+        let back: Series<i32, String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.data, vec![1, 2]);
+        assert_eq!(back.name.as_deref(), Some("n"));
+        assert_eq!(back.index_name, None);
+    }
+
+    #[test]
+    fn json_without_names_still_reads() {
+        let back: Series<f64> = serde_json::from_str(r#"{"data":[0.5],"label":[0]}"#).unwrap();
+        assert_eq!(back.as_parts(), (&[0.5][..], &[0][..]));
+        assert_eq!((back.name, back.index_name), (None, None));
+    }
+
+    #[test]
+    fn round_trips_through_json_and_bincode() {
+        let s = Series::from_label(&[1.5, 2.0], &["a".to_string(), "b".to_string()])
+            .with_index_name("key");
+        let json = serde_json::to_string(&s).unwrap();
+        let back: Series<f64, String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.as_parts(), s.as_parts());
+        assert_eq!(back.index_name.as_deref(), Some("key"));
+
+        let bytes = bincode::serialize(&s).unwrap();
+        let back: Series<f64, String> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(back.as_parts(), s.as_parts());
+        assert_eq!(back.index_name.as_deref(), Some("key"));
+    }
+
+    #[test]
+    fn rejects_mismatched_lengths() {
+        let err =
+            serde_json::from_str::<Series<f64, String>>(r#"{"data":[1.5],"label":["a","b"]}"#)
+                .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("data has 1 elements but label has 2"));
+    }
+}
@@ -0,0 +1,452 @@
+use std::io::{Read, Write};
+
+use crate::{
+    data::Series,
+    error::RudasError,
+    frame::{Column, DType, DataFrame},
+};
+
+/// Leading bytes of every snapshot, followed by `VERSION` and a byte telling
+/// a series from a frame.
+pub const MAGIC: &[u8; 4] = b"RDAS";
+/// Version 2 added the names of a series. Version 1 snapshots still load,
+/// without names.
+pub const VERSION: u8 = 2;
+
+const SERIES: u8 = b'S';
+const FRAME: u8 = b'F';
+
+/// Types that can be stored in a snapshot.
+pub trait SnapshotValue: Sized {
+    /// Identifies the type in the snapshot header.
+    const TAG: u8;
+
+    fn write_values<W: Write>(values: &[Self], writer: &mut W) -> Result<(), RudasError>;
+    fn read_values<R: Read>(reader: &mut R, len: usize) -> Result<Vec<Self>, RudasError>;
+}
+
+fn corrupt(message: &str) -> RudasError {
+    RudasError::InvalidFormat(message.to_string())
+}
+
+/// Reads exactly `len` bytes without trusting `len` for the allocation, so
+/// a corrupt length fails on the missing bytes instead of exhausting memory.
+fn read_bytes<R: Read>(reader: &mut R, len: u64) -> Result<Vec<u8>, RudasError> {
+    let mut buf = Vec::new();
+    reader.take(len).read_to_end(&mut buf)?;
+    if buf.len() as u64 != len {
+        return Err(corrupt("unexpected end of input"));
+    }
+    Ok(buf)
+}
+
+fn read_u8<R: Read>(reader: &mut R) -> Result<u8, RudasError> {
+    Ok(read_bytes(reader, 1)?[0])
+}
+
+fn read_len<R: Read>(reader: &mut R) -> Result<usize, RudasError> {
+    let bytes = read_bytes(reader, 8)?;
+    let len = u64::from_le_bytes(bytes.try_into().unwrap());
+    usize::try_from(len).map_err(|_| corrupt("length does not fit in memory"))
+}
+
+fn write_len<W: Write>(writer: &mut W, len: usize) -> Result<(), RudasError> {
+    writer.write_all(&(len as u64).to_le_bytes())?;
+    Ok(())
+}
+
+macro_rules! impl_snapshot_value {
+    ($($t:ty => $tag:expr),*) => {
+        $(
+            impl SnapshotValue for $t {
+                const TAG: u8 = $tag;
+
+                fn write_values<W: Write>(values: &[Self], writer: &mut W) -> Result<(), RudasError> {
+                    let mut buf = Vec::with_capacity(values.len() * size_of::<$t>());
+                    for x in values {
+                        buf.extend_from_slice(&x.to_le_bytes());
+                    }
+                    writer.write_all(&buf)?;
+                    Ok(())
+                }
+
+                fn read_values<R: Read>(reader: &mut R, len: usize) -> Result<Vec<Self>, RudasError> {
+                    let size = size_of::<$t>();
+                    let bytes = (len as u64)
+                        .checked_mul(size as u64)
+                        .ok_or_else(|| corrupt("length does not fit in memory"))?;
+                    Ok(read_bytes(reader, bytes)?
+                        .chunks_exact(size)
+                        .map(|chunk| <$t>::from_le_bytes(chunk.try_into().unwrap()))
+                        .collect())
+                }
+            }
+        )*
+    };
+}
+
+impl_snapshot_value!(
+    i8 => 1, i16 => 2, i32 => 3, i64 => 4,
+    u8 => 5, u16 => 6, u32 => 7, u64 => 8,
+    f32 => 9, f64 => 10
+);
+
+impl SnapshotValue for usize {
+    const TAG: u8 = 11;
+
+    fn write_values<W: Write>(values: &[Self], writer: &mut W) -> Result<(), RudasError> {
+        let values: Vec<u64> = values.iter().map(|&x| x as u64).collect();
+        u64::write_values(&values, writer)
+    }
+
+    fn read_values<R: Read>(reader: &mut R, len: usize) -> Result<Vec<Self>, RudasError> {
+        u64::read_values(reader, len)?
+            .into_iter()
+            .map(|x| usize::try_from(x).map_err(|_| corrupt("usize value out of range")))
+            .collect()
+    }
+}
+
+impl SnapshotValue for bool {
+    const TAG: u8 = 12;
+
+    fn write_values<W: Write>(values: &[Self], writer: &mut W) -> Result<(), RudasError> {
+        let bytes: Vec<u8> = values.iter().map(|&x| x as u8).collect();
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    fn read_values<R: Read>(reader: &mut R, len: usize) -> Result<Vec<Self>, RudasError> {
+        read_bytes(reader, len as u64)?
+            .into_iter()
+            .map(|b| match b {
+                0 => Ok(false),
+                1 => Ok(true),
+                _ => Err(corrupt("invalid bool")),
+            })
+            .collect()
+    }
+}
+
+impl SnapshotValue for String {
+    const TAG: u8 = 13;
+
+    fn write_values<W: Write>(values: &[Self], writer: &mut W) -> Result<(), RudasError> {
+        let mut buf = Vec::new();
+        for s in values {
+            buf.extend_from_slice(&(s.len() as u64).to_le_bytes());
+            buf.extend_from_slice(s.as_bytes());
+        }
+        writer.write_all(&buf)?;
+        Ok(())
+    }
+
+    fn read_values<R: Read>(reader: &mut R, len: usize) -> Result<Vec<Self>, RudasError> {
+        let mut values = Vec::new();
+        for _ in 0..len {
+            let n = read_len(reader)?;
+            let bytes = read_bytes(reader, n as u64)?;
+            values.push(String::from_utf8(bytes).map_err(|_| corrupt("invalid UTF-8 string"))?);
+        }
+        Ok(values)
+    }
+}
+
+fn write_header<W: Write>(writer: &mut W, kind: u8) -> Result<(), RudasError> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[VERSION, kind])?;
+    Ok(())
+}
+
+/// Checks the magic bytes and the kind, and returns the version.
+fn read_header<R: Read>(reader: &mut R, kind: u8) -> Result<u8, RudasError> {
+    if read_bytes(reader, 4)? != MAGIC {
+        return Err(corrupt("not a rudas snapshot"));
+    }
+    let version = read_u8(reader)?;
+    if !(1..=VERSION).contains(&version) {
+        return Err(RudasError::InvalidFormat(format!(
+            "unsupported snapshot version {}",
+            version
+        )));
+    }
+    if read_u8(reader)? != kind {
+        let expected = if kind == SERIES { "series" } else { "frame" };
+        return Err(RudasError::InvalidFormat(format!(
+            "snapshot does not hold a {}",
+            expected
+        )));
+    }
+    Ok(version)
+}
+
+fn write_name<W: Write>(writer: &mut W, name: &Option<String>) -> Result<(), RudasError> {
+    bool::write_values(&[name.is_some()], writer)?;
+    String::write_values(name.as_slice(), writer)
+}
+
+fn read_name<R: Read>(reader: &mut R) -> Result<Option<String>, RudasError> {
+    let present = bool::read_values(reader, 1)?[0];
+    Ok(String::read_values(reader, present as usize)?.pop())
+}
+
+fn check_tag(found: u8, expected: u8, what: &str) -> Result<(), RudasError> {
+    if found != expected {
+        return Err(RudasError::InvalidFormat(format!(
+            "{} type tag is {}, expected {}",
+            what, found, expected
+        )));
+    }
+    Ok(())
+}
+
+impl<T, U> Series<T, U>
+where
+    T: Clone + SnapshotValue,
+    U: PartialEq + Clone + SnapshotValue,
+{
+    /// Writes the series as a binary snapshot: the type tags of the values and
+    /// labels, `name` and `index_name`, the length and both buffers. Numbers
+    /// are little-endian, strings are prefixed with their length in bytes
+    /// and each name with a byte telling whether it is set.
+    pub fn save<W: Write>(&self, mut writer: W) -> Result<(), RudasError> {
+        if self.data.len() != self.label.len() {
+            return Err(RudasError::LengthMismatch {
+                expected: self.label.len(),
+                found: self.data.len(),
+            });
+        }
+        write_header(&mut writer, SERIES)?;
+        writer.write_all(&[T::TAG, U::TAG])?;
+        write_name(&mut writer, &self.name)?;
+        write_name(&mut writer, &self.index_name)?;
+        write_len(&mut writer, self.len())?;
+        T::write_values(&self.data, &mut writer)?;
+        U::write_values(&self.label, &mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a series written by `save`, checking that the stored types are
+    /// `T` and `U`.
+    pub fn load<R: Read>(mut reader: R) -> Result<Series<T, U>, RudasError> {
+        let version = read_header(&mut reader, SERIES)?;
+        check_tag(read_u8(&mut reader)?, T::TAG, "value")?;
+        check_tag(read_u8(&mut reader)?, U::TAG, "label")?;
+        let (name, index_name) = match version {
+            1 => (None, None),
+            _ => (read_name(&mut reader)?, read_name(&mut reader)?),
+        };
+        let len = read_len(&mut reader)?;
+        let data = T::read_values(&mut reader, len)?;
+        let label = U::read_values(&mut reader, len)?;
+        Ok(Series {
+            name,
+            index_name,
+            ..Series::from_parts(data, label)
+        })
+    }
+}
+
+fn dtype_tag(dtype: DType) -> u8 {
+    match dtype {
+        DType::I64 => i64::TAG,
+        DType::F64 => f64::TAG,
+        DType::Bool => bool::TAG,
+        DType::Str => String::TAG,
+    }
+}
+
+fn write_cells<T, W>(cells: &[Option<T>], writer: &mut W) -> Result<(), RudasError>
+where
+    T: SnapshotValue + Clone + Default,
+    W: Write,
+{
+    let present: Vec<u8> = cells.iter().map(|c| c.is_some() as u8).collect();
+    writer.write_all(&present)?;
+    let values: Vec<T> = cells
+        .iter()
+        .map(|c| c.clone().unwrap_or_default())
+        .collect();
+    T::write_values(&values, writer)
+}
+
+fn read_cells<T: SnapshotValue, R: Read>(
+    reader: &mut R,
+    len: usize,
+) -> Result<Vec<Option<T>>, RudasError> {
+    let present = bool::read_values(reader, len)?;
+    let values = T::read_values(reader, len)?;
+    Ok(present
+        .into_iter()
+        .zip(values)
+        .map(|(p, v)| p.then_some(v))
+        .collect())
+}
+
+impl DataFrame {
+    /// Writes the frame as a binary snapshot: the row and column counts, the
+    /// labels, then for every column its name, type tag, one presence byte
+    /// per row and the values with missing cells zeroed.
+    pub fn save<W: Write>(&self, mut writer: W) -> Result<(), RudasError> {
+        write_header(&mut writer, FRAME)?;
+        write_len(&mut writer, self.len())?;
+        write_len(&mut writer, self.width())?;
+        String::write_values(self.label(), &mut writer)?;
+        for (name, column) in self.columns() {
+            String::write_values(std::slice::from_ref(name), &mut writer)?;
+            writer.write_all(&[dtype_tag(column.dtype())])?;
+            match column {
+                Column::I64(v) => write_cells(v, &mut writer)?,
+                Column::F64(v) => write_cells(v, &mut writer)?,
+                Column::Bool(v) => write_cells(v, &mut writer)?,
+                Column::Str(v) => write_cells(v, &mut writer)?,
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a frame written by `save`.
+    pub fn load<R: Read>(mut reader: R) -> Result<DataFrame, RudasError> {
+        read_header(&mut reader, FRAME)?;
+        let len = read_len(&mut reader)?;
+        let width = read_len(&mut reader)?;
+        let label = String::read_values(&mut reader, len)?;
+
+        let mut df = DataFrame::new();
+        for _ in 0..width {
+            let name = String::read_values(&mut reader, 1)?.remove(0);
+            let column = match read_u8(&mut reader)? {
+                tag if tag == i64::TAG => Column::I64(read_cells(&mut reader, len)?),
+                tag if tag == f64::TAG => Column::F64(read_cells(&mut reader, len)?),
+                tag if tag == bool::TAG => Column::Bool(read_cells(&mut reader, len)?),
+                tag if tag == String::TAG => Column::Str(read_cells(&mut reader, len)?),
+                tag => {
+                    return Err(RudasError::InvalidFormat(format!(
+                        "unknown column type tag {}",
+                        tag
+                    )))
+                }
+            };
+            df.add_column(&name, column)?;
+        }
+        df.set_label(label)?;
+        Ok(df)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn series_round_trips_with_names() {
+        let s = Series::from_label(&[1.5, -2.0], &["a".to_string(), "b".to_string()])
+            .with_name("price")
+            .with_index_name("ticker");
+        let mut buf = Vec::new();
+        s.save(&mut buf).unwrap();
+        let loaded = Series::<f64, String>::load(&buf[..]).unwrap();
+        assert_eq!(loaded.as_parts(), s.as_parts());
+        assert_eq!(loaded.name.as_deref(), Some("price"));
+        assert_eq!(loaded.index_name.as_deref(), Some("ticker"));
+
+        let unnamed = Series::from(&[1i64, 2]);
+        buf.clear();
+        unnamed.save(&mut buf).unwrap();
+        let loaded = Series::<i64>::load(&buf[..]).unwrap();
+        assert_eq!((loaded.name, loaded.index_name), (None, None));
+    }
+
+    #[test]
+    fn loads_version_one_series_without_names() {
+        let mut buf = MAGIC.to_vec();
+        buf.extend_from_slice(&[1, SERIES, i32::TAG, u8::TAG]);
+        buf.extend_from_slice(&2u64.to_le_bytes());
+        buf.extend_from_slice(&7i32.to_le_bytes());
+        buf.extend_from_slice(&(-1i32).to_le_bytes());
+        buf.extend_from_slice(&[3, 4]);
+        let loaded = Series::<i32, u8>::load(&buf[..]).unwrap();
+        assert_eq!(loaded.as_parts(), (&[7, -1][..], &[3, 4][..]));
+        assert_eq!(loaded.name, None);
+
+        buf[4] = VERSION + 1;
+        assert!(matches!(
+            Series::<i32, u8>::load(&buf[..]),
+            Err(RudasError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn series_load_checks_types_and_rejects_corrupt_input() {
+        let s = Series::from_label(
+            &[1.5, -2.0, f64::NAN],
+            &["a".to_string(), "bé".into(), "".into()],
+        );
+        let mut buf = Vec::new();
+        s.save(&mut buf).unwrap();
+        let back = Series::<f64, String>::load(&buf[..]).unwrap();
+        assert_eq!(back.label, s.label);
+        assert_eq!(back.data[..2], s.data[..2]);
+        assert!(back.data[2].is_nan());
+
+        assert!(Series::<i64, String>::load(&buf[..])
+            .unwrap_err()
+            .to_string()
+            .contains("value type tag"));
+        assert!(Series::<f64, usize>::load(&buf[..]).is_err());
+        for cut in [0, 3, 5, 8, 20, buf.len() - 1] {
+            assert!(Series::<f64, String>::load(&buf[..cut]).is_err(), "{}", cut);
+        }
+        let mut bad = buf.clone();
+        bad[0] = b'X';
+        assert!(Series::<f64, String>::load(&bad[..])
+            .unwrap_err()
+            .to_string()
+            .contains("not a rudas snapshot"));
+        // Header, two type tags and two unset names come before the length.
+        let mut huge = buf.clone();
+        huge[10..18].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(Series::<f64, String>::load(&huge[..]).is_err());
+
+        let flags = Series::from(&[true, false]);
+        buf.clear();
+        flags.save(&mut buf).unwrap();
+        assert_eq!(
+            Series::<bool>::load(&buf[..]).unwrap().data,
+            vec![true, false]
+        );
+    }
+
+    #[test]
+    fn frame_round_trips_with_missing_cells() {
+        let mut df = DataFrame::from_columns(vec![
+            ("i", Column::from(vec![Some(1i64), None, Some(3)])),
+            (
+                "f",
+                Column::from(vec![Some(0.5), Some(f64::INFINITY), None]),
+            ),
+            ("b", Column::from(vec![None, Some(true), Some(false)])),
+            (
+                "s",
+                Column::from(vec![Some("x".to_string()), None, Some(String::new())]),
+            ),
+        ])
+        .unwrap();
+        df.set_label(vec!["r1".into(), "r2".into(), "r3".into()])
+            .unwrap();
+        let mut buf = Vec::new();
+        df.save(&mut buf).unwrap();
+        assert_eq!(DataFrame::load(&buf[..]).unwrap(), df);
+        assert!(DataFrame::load(&buf[..buf.len() - 2]).is_err());
+
+        let mut series = Vec::new();
+        Series::from(&[1.0]).save(&mut series).unwrap();
+        assert!(DataFrame::load(&series[..]).is_err());
+
+        buf.clear();
+        DataFrame::new().save(&mut buf).unwrap();
+        assert!(DataFrame::load(&buf[..]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{data::Series, num::Numeric};
+
+/// Running statistics over values that arrive in chunks, such as the frames
+/// yielded by `read_csv_chunks`, without keeping the values.
+///
+/// Accumulators fed with different parts of the data can be combined with
+/// `merge`, so chunks may be processed in parallel. Statistics that need a
+/// sketch, like approximate quantiles, can follow the same
+/// `push_chunk`/`merge`/`finish` shape.
+///
+/// The sum uses `Numeric::checked_add` and is forgotten once it overflows
+/// `T`; the other statistics are kept in `f64` and do not overflow.
+#[derive(Debug, Clone)]
+pub struct Accumulator<T: Numeric> {
+    count: usize,
+    sum: Option<T>,
+    min: Option<T>,
+    max: Option<T>,
+    mean: f64,
+    m2: f64,
+}
+
+impl<T: Numeric> Accumulator<T> {
+    pub fn new() -> Accumulator<T> {
+        Accumulator {
+            count: 0,
+            sum: Some(T::zero()),
+            min: None,
+            max: None,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    /// An accumulator that has seen every value of `series`.
+    pub fn from_series<U: PartialEq + Clone>(series: &Series<T, U>) -> Accumulator<T> {
+        let mut acc = Accumulator::new();
+        acc.push_chunk(series);
+        acc
+    }
+
+    pub fn push(&mut self, x: T) {
+        self.count += 1;
+        self.sum = self.sum.and_then(|sum| sum.checked_add(x));
+        self.min = Some(self.min.map_or(x, |m| if x < m { x } else { m }));
+        self.max = Some(self.max.map_or(x, |m| if x > m { x } else { m }));
+        let delta = x.to_f64() - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x.to_f64() - self.mean);
+    }
+
+    /// Like `push`, for callers that hold references to the values.
+    pub fn update(&mut self, x: &T) {
+        self.push(*x);
+    }
+
+    pub fn push_chunk<U: PartialEq + Clone>(&mut self, chunk: &Series<T, U>) {
+        for &x in &chunk.data {
+            self.push(x);
+        }
+    }
+
+    /// Adds the values seen by `other`, as if they had been pushed here.
+    pub fn merge(&mut self, other: &Accumulator<T>) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = other.clone();
+            return;
+        }
+        let (n, m) = (self.count as f64, other.count as f64);
+        let delta = other.mean - self.mean;
+        self.mean += delta * m / (n + m);
+        self.m2 += other.m2 + delta * delta * n * m / (n + m);
+        self.count += other.count;
+        self.sum = self.sum.zip(other.sum).and_then(|(a, b)| a.checked_add(b));
+        self.min = pick(self.min, other.min, |a, b| a < b);
+        self.max = pick(self.max, other.max, |a, b| a > b);
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `None` once the sum has overflowed `T`.
+    pub fn sum(&self) -> Option<T> {
+        self.sum
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.max
+    }
+
+    /// Returns `NAN` when no value was pushed.
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.mean
+        }
+    }
+
+    /// Variance with `ddof` delta degrees of freedom. Returns `NAN` when
+    /// there are not more than `ddof` values.
+    pub fn var(&self, ddof: usize) -> f64 {
+        if self.count <= ddof {
+            f64::NAN
+        } else {
+            self.m2 / (self.count - ddof) as f64
+        }
+    }
+
+    /// Sample variance, `var(1)`.
+    pub fn variance(&self) -> f64 {
+        self.var(1)
+    }
+
+    /// Statistics labeled `count`, `sum`, `mean`, `std`, `min` and `max`.
+    /// `std` uses `ddof = 1` and an overflowed `sum` is `NAN`.
+    pub fn finish(&self) -> Series<f64, String> {
+        let or_nan = |x: Option<T>| x.map_or(f64::NAN, |x| x.to_f64());
+        let stats = [
+            ("count", self.count as f64),
+            ("sum", or_nan(self.sum)),
+            ("mean", self.mean()),
+            ("std", self.var(1).sqrt()),
+            ("min", or_nan(self.min)),
+            ("max", or_nan(self.max)),
+        ];
+        Series::from_parts(
+            stats.iter().map(|(_, v)| *v).collect(),
+            stats.iter().map(|(l, _)| l.to_string()).collect(),
+        )
+    }
+}
+
+/// `Accumulator` under the name used when feeding values one at a time
+/// with `update`.
+pub type SeriesAggregator<T> = Accumulator<T>;
+
+impl<T: Numeric> Default for Accumulator<T> {
+    fn default() -> Accumulator<T> {
+        Accumulator::new()
+    }
+}
+
+fn pick<T: Copy>(a: Option<T>, b: Option<T>, keep_b: fn(&T, &T) -> bool) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if keep_b(&b, &a) { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Number of occurrences of every value across chunks, in the order the
+/// values are first seen.
+#[derive(Debug, Clone)]
+pub struct ValueCounter<T: Hash + Eq + Clone> {
+    index: HashMap<T, usize>,
+    values: Vec<T>,
+    counts: Vec<usize>,
+}
+
+impl<T: Hash + Eq + Clone> ValueCounter<T> {
+    pub fn new() -> ValueCounter<T> {
+        ValueCounter {
+            index: HashMap::new(),
+            values: Vec::new(),
+            counts: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, value: &T) {
+        self.add(value, 1);
+    }
+
+    fn add(&mut self, value: &T, n: usize) {
+        match self.index.get(value) {
+            Some(&i) => self.counts[i] += n,
+            None => {
+                self.index.insert(value.clone(), self.values.len());
+                self.values.push(value.clone());
+                self.counts.push(n);
+            }
+        }
+    }
+
+    pub fn push_chunk<U: PartialEq + Clone>(&mut self, chunk: &Series<T, U>) {
+        for value in &chunk.data {
+            self.push(value);
+        }
+    }
+
+    pub fn merge(&mut self, other: &ValueCounter<T>) {
+        for (value, &n) in other.values.iter().zip(&other.counts) {
+            self.add(value, n);
+        }
+    }
+
+    /// The counts labeled by value.
+    pub fn finish(&self) -> Series<usize, T> {
+        Series::from_parts(self.counts.clone(), self.values.clone())
+    }
+}
+
+impl<T: Hash + Eq + Clone> Default for ValueCounter<T> {
+    fn default() -> ValueCounter<T> {
+        ValueCounter::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregator_matches_series_statistics() {
+        let s = Series::from(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        let mut agg = SeriesAggregator::new();
+        for x in &s.data {
+            agg.update(x);
+        }
+        assert_eq!(agg.count(), 8);
+        assert_eq!(agg.sum(), Some(40.0));
+        assert_eq!(agg.mean(), 5.0);
+        assert!((agg.var(0) - 4.0).abs() < 1e-12);
+        assert!((agg.variance() - 32.0 / 7.0).abs() < 1e-12);
+        assert_eq!((agg.min(), agg.max()), (Some(2.0), Some(9.0)));
+
+        let mut merged = Accumulator::from_series(&s.head(3));
+        merged.merge(&Accumulator::from_series(&s.tail(5)));
+        assert_eq!(merged.sum(), agg.sum());
+        assert!((merged.variance() - agg.variance()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn empty_aggregator_has_nan_statistics() {
+        let agg = Accumulator::<i64>::new();
+        assert_eq!(agg.sum(), Some(0));
+        assert!(agg.mean().is_nan() && agg.variance().is_nan());
+        assert_eq!(agg.min(), None);
+    }
+
+    #[test]
+    fn overflowing_sum_is_dropped() {
+        let mut agg = Accumulator::from_series(&Series::from(&[i8::MAX, 1, -100]));
+        assert_eq!(agg.sum(), None);
+        assert_eq!(agg.count(), 3);
+        assert!((agg.mean() - 28.0 / 3.0).abs() < 1e-12);
+        assert!(agg.finish().data[1].is_nan());
+
+        agg = Accumulator::from_series(&Series::from(&[100i8]));
+        agg.merge(&Accumulator::from_series(&Series::from(&[100i8])));
+        assert_eq!(agg.sum(), None);
+        assert_eq!(agg.max(), Some(100));
+    }
+
+    #[test]
+    fn merged_chunks_match_the_whole_series() {
+        let values: Vec<f64> = (0..25).map(|i| (i * 7 % 11) as f64).collect();
+        let whole = Series::from(&values);
+        let mut merged = Accumulator::new();
+        for chunk in values.chunks(10) {
+            merged.merge(&Accumulator::from_series(&Series::from(chunk)));
+        }
+        assert_eq!(merged.count(), 25);
+        assert!((merged.mean() - whole.mean()).abs() < 1e-12);
+        assert!((merged.var(1) - whole.var(1)).abs() < 1e-9);
+        assert_eq!((merged.min(), merged.max()), (Some(0.0), Some(10.0)));
+        assert_eq!(
+            merged.finish().label,
+            vec!["count", "sum", "mean", "std", "min", "max"]
+        );
+    }
+
+    #[test]
+    fn value_counter_merges_in_first_seen_order() {
+        let keys = ["b", "a", "b", "c", "a", "b"];
+        let mut first = ValueCounter::new();
+        first.push_chunk(&Series::from(&keys[..3]));
+        let mut second = ValueCounter::new();
+        second.push_chunk(&Series::from(&keys[3..]));
+        first.merge(&second);
+        let counts = first.finish();
+        assert_eq!(counts.as_parts(), (&[3, 2, 1][..], &["b", "a", "c"][..]));
+    }
+}
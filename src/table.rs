@@ -0,0 +1,291 @@
+use std::fmt::Display;
+
+use crate::{
+    data::Series,
+    frame::{Column, DataFrame},
+    record::Field,
+};
+
+const MISSING: &str = "null";
+const ELLIPSIS: &str = "...";
+
+#[derive(Debug, Clone)]
+pub struct TableOptions {
+    /// Digits after the decimal point for `f64` cells.
+    pub float_precision: Option<usize>,
+    /// When the frame is longer, only the first and last rows are shown
+    /// around an ellipsis row.
+    pub max_rows: Option<usize>,
+    /// Show the row labels as the first column.
+    pub show_label: bool,
+}
+
+impl Default for TableOptions {
+    fn default() -> TableOptions {
+        TableOptions {
+            float_precision: None,
+            max_rows: None,
+            show_label: true,
+        }
+    }
+}
+
+/// The text of a rendered table. A `None` row stands for omitted rows.
+struct Table {
+    header: Vec<String>,
+    numeric: Vec<bool>,
+    rows: Vec<Option<Vec<String>>>,
+}
+
+impl Table {
+    fn new(df: &DataFrame, options: &TableOptions) -> Table {
+        let mut header = Vec::new();
+        let mut numeric = Vec::new();
+        if options.show_label {
+            header.push(String::new());
+            numeric.push(false);
+        }
+        for (name, column) in df.columns() {
+            header.push(name.clone());
+            numeric.push(matches!(column, Column::I64(_) | Column::F64(_)));
+        }
+
+        let n = df.len();
+        let positions: Vec<Option<usize>> = match options.max_rows {
+            Some(max) if n > max => {
+                let head = max.div_ceil(2);
+                (0..head)
+                    .map(Some)
+                    .chain([None])
+                    .chain((n - (max - head)..n).map(Some))
+                    .collect()
+            }
+            _ => (0..n).map(Some).collect(),
+        };
+
+        let rows = positions
+            .into_iter()
+            .map(|p| {
+                p.map(|i| {
+                    let label = options.show_label.then(|| df.label()[i].clone());
+                    let cells = df.columns().iter().map(|(_, column)| {
+                        column
+                            .format_cell(i, options.float_precision)
+                            .unwrap_or_else(|| MISSING.to_string())
+                    });
+                    label.into_iter().chain(cells).collect()
+                })
+            })
+            .collect();
+
+        Table {
+            header,
+            numeric,
+            rows,
+        }
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.header.iter().map(|h| h.chars().count()).collect();
+        for row in self.rows.iter().flatten() {
+            for (w, cell) in widths.iter_mut().zip(row) {
+                *w = (*w).max(cell.chars().count());
+            }
+        }
+        widths
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+impl DataFrame {
+    /// Renders the frame as a GitHub-flavored Markdown table. Numeric columns
+    /// are right-aligned.
+    pub fn to_markdown(&self, options: &TableOptions) -> String {
+        let table = Table::new(self, options);
+        let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+
+        let mut out = line(table.header.iter().map(|h| escape_markdown(h)).collect());
+        out += &line(
+            table
+                .numeric
+                .iter()
+                .map(|&n| if n { "---:" } else { "---" }.to_string())
+                .collect(),
+        );
+        for row in &table.rows {
+            out += &line(match row {
+                Some(cells) => cells.iter().map(|c| escape_markdown(c)).collect(),
+                None => vec![ELLIPSIS.to_string(); table.header.len()],
+            });
+        }
+        out
+    }
+
+    /// Renders the frame as an HTML `<table>`, with the row labels as row
+    /// headers.
+    pub fn to_html(&self, options: &TableOptions) -> String {
+        let table = Table::new(self, options);
+        let label = usize::from(options.show_label);
+
+        let mut out = String::from("<table>\n  <thead>\n    <tr>");
+        for h in &table.header {
+            out += &format!("<th>{}</th>", escape_html(h));
+        }
+        out += "</tr>\n  </thead>\n  <tbody>\n";
+        for row in &table.rows {
+            out += "    <tr>";
+            match row {
+                Some(cells) => {
+                    for (j, cell) in cells.iter().enumerate() {
+                        let tag = if j < label { "th" } else { "td" };
+                        out += &format!("<{}>{}</{}>", tag, escape_html(cell), tag);
+                    }
+                }
+                None => {
+                    for _ in 0..table.header.len() {
+                        out += &format!("<td>{}</td>", ELLIPSIS);
+                    }
+                }
+            }
+            out += "</tr>\n";
+        }
+        out += "  </tbody>\n</table>\n";
+        out
+    }
+}
+
+/// Renders an aligned plain-text table, with the same cell text as
+/// `to_markdown` and `to_html`.
+impl Display for DataFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let table = Table::new(self, &TableOptions::default());
+        let widths = table.widths();
+        let mut write_row = |cells: &[String]| -> std::fmt::Result {
+            let line: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .zip(&table.numeric)
+                .map(|((cell, &w), &numeric)| {
+                    if numeric {
+                        format!("{:>w$}", cell, w = w)
+                    } else {
+                        format!("{:<w$}", cell, w = w)
+                    }
+                })
+                .collect();
+            writeln!(f, "{}", line.join("  ").trim_end())
+        };
+        write_row(&table.header)?;
+        for row in table.rows.iter().flatten() {
+            write_row(row)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Field + Clone, U: PartialEq + Clone + Display> Series<T, U> {
+    /// Renders the series as a Markdown table with a single `value` column.
+    pub fn to_markdown(&self, options: &TableOptions) -> String {
+        self.to_frame("value").to_markdown(options)
+    }
+
+    /// Renders the series as an HTML table with a single `value` column.
+    pub fn to_html(&self, options: &TableOptions) -> String {
+        self.to_frame("value").to_html(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mixed_frame() -> DataFrame {
+        DataFrame::from_columns(vec![
+            (
+                "name",
+                Column::Str(vec![Some("a|b".into()), Some("<x>&".into()), None]),
+            ),
+            ("f", Column::F64(vec![Some(1.0), None, Some(2.345)])),
+            ("i", Column::from(vec![1i64, 20, 300])),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn markdown_snapshot() {
+        let df = mixed_frame();
+        assert_eq!(
+            df.to_markdown(&TableOptions::default()),
+            "|  | name | f | i |\n\
+             | --- | --- | ---: | ---: |\n\
+             | 0 | a\\|b | 1.0 | 1 |\n\
+             | 1 | <x>& | null | 20 |\n\
+             | 2 | null | 2.345 | 300 |\n"
+        );
+        let options = TableOptions {
+            float_precision: Some(1),
+            max_rows: Some(2),
+            show_label: false,
+        };
+        assert_eq!(
+            df.to_markdown(&options),
+            "| name | f | i |\n\
+             | --- | ---: | ---: |\n\
+             | a\\|b | 1.0 | 1 |\n\
+             | ... | ... | ... |\n\
+             | null | 2.3 | 300 |\n"
+        );
+    }
+
+    #[test]
+    fn html_snapshot() {
+        assert_eq!(
+            mixed_frame().to_html(&TableOptions::default()),
+            concat!(
+                "<table>\n",
+                "  <thead>\n",
+                "    <tr><th></th><th>name</th><th>f</th><th>i</th></tr>\n",
+                "  </thead>\n",
+                "  <tbody>\n",
+                "    <tr><th>0</th><td>a|b</td><td>1.0</td><td>1</td></tr>\n",
+                "    <tr><th>1</th><td>&lt;x&gt;&amp;</td><td>null</td><td>20</td></tr>\n",
+                "    <tr><th>2</th><td>null</td><td>2.345</td><td>300</td></tr>\n",
+                "  </tbody>\n",
+                "</table>\n",
+            )
+        );
+    }
+
+    #[test]
+    fn display_snapshot() {
+        assert_eq!(
+            mixed_frame().to_string(),
+            "   name      f    i\n\
+             0  a|b     1.0    1\n\
+             1  <x>&   null   20\n\
+             2  null  2.345  300\n"
+        );
+        let s = Series::from(&[1i64]).to_markdown(&TableOptions::default());
+        assert!(s.contains("| 0 | 1 |"));
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt::{Debug, Write};
+
+use crate::{data::Series, num::Numeric};
+
+/// Number of differing positions listed by `check_series_eq`.
+const MAX_REPORTED: usize = 5;
+
+fn close(a: f64, b: f64, abs_tol: f64, rel_tol: f64, nan_equal: bool) -> bool {
+    if a.is_nan() || b.is_nan() {
+        return nan_equal && a.is_nan() && b.is_nan();
+    }
+    a == b || (a - b).abs() <= abs_tol.max(rel_tol * a.abs().max(b.abs()))
+}
+
+impl<T: Numeric, U: PartialEq + Clone> Series<T, U> {
+    /// Whether both series have the same labels and every pair of values
+    /// differs by at most `abs_tol` or `rel_tol` times the larger magnitude.
+    /// Two `NAN`s are equal only when `nan_equal` is set.
+    pub fn approx_eq(
+        &self,
+        other: &Series<T, U>,
+        abs_tol: f64,
+        rel_tol: f64,
+        nan_equal: bool,
+    ) -> bool {
+        self.label == other.label
+            && self
+                .data
+                .iter()
+                .zip(&other.data)
+                .all(|(a, b)| close(a.to_f64(), b.to_f64(), abs_tol, rel_tol, nan_equal))
+    }
+}
+
+/// Compares two series like `approx_eq` and describes the first differences,
+/// with their positions, labels and values, when they are not equal.
+pub fn check_series_eq<T, U>(
+    left: &Series<T, U>,
+    right: &Series<T, U>,
+    abs_tol: f64,
+    rel_tol: f64,
+    nan_equal: bool,
+) -> Result<(), String>
+where
+    T: Numeric + Debug,
+    U: PartialEq + Clone + Debug,
+{
+    if left.len() != right.len() {
+        return Err(format!(
+            "series lengths differ : left {}, right {}",
+            left.len(),
+            right.len()
+        ));
+    }
+    let mut diffs = (0..left.len()).filter(|&i| {
+        left.label[i] != right.label[i]
+            || !close(
+                left.data[i].to_f64(),
+                right.data[i].to_f64(),
+                abs_tol,
+                rel_tol,
+                nan_equal,
+            )
+    });
+    let first: Vec<usize> = diffs.by_ref().take(MAX_REPORTED).collect();
+    if first.is_empty() {
+        return Ok(());
+    }
+    let more = diffs.count();
+
+    let mut message = format!("series differ at {} positions", first.len() + more);
+    for i in first {
+        let _ = write!(
+            message,
+            "\n  [{}] left {:?} = {:?}, right {:?} = {:?}",
+            i, left.label[i], left.data[i], right.label[i], right.data[i]
+        );
+    }
+    if more > 0 {
+        let _ = write!(message, "\n  ... and {} more", more);
+    }
+    Err(message)
+}
+
+/// Asserts that two series are equal, printing the first differing positions
+/// on failure. Values are compared exactly unless tolerances are given, and
+/// two `NAN`s are equal unless `nan_equal` is passed as false.
+///
+/// ```ignore
+/// assert_series_eq!(left, right);
+/// assert_series_eq!(left, right, 1e-9, 1e-6);
+/// assert_series_eq!(left, right, 1e-9, 1e-6, false);
+/// ```
+#[macro_export]
+macro_rules! assert_series_eq {
+    ($left:expr, $right:expr) => {
+        $crate::assert_series_eq!($left, $right, 0.0, 0.0, true)
+    };
+    ($left:expr, $right:expr, $abs_tol:expr, $rel_tol:expr) => {
+        $crate::assert_series_eq!($left, $right, $abs_tol, $rel_tol, true)
+    };
+    ($left:expr, $right:expr, $abs_tol:expr, $rel_tol:expr, $nan_equal:expr) => {
+        if let Err(message) =
+            $crate::testing::check_series_eq(&$left, &$right, $abs_tol, $rel_tol, $nan_equal)
+        {
+            panic!("{}", message);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approx_eq_uses_tolerances_and_nan_equality() {
+        let a = Series::from(&[1.0, 2.0, f64::NAN]);
+        let b = Series::from(&[1.0 + 1e-12, 2.0, f64::NAN]);
+        assert!(a.approx_eq(&b, 1e-9, 0.0, true));
+        assert!(!a.approx_eq(&b, 1e-9, 0.0, false));
+        assert!(!a.approx_eq(&b, 0.0, 0.0, true));
+        assert!(a.approx_eq(&b, 0.0, 1e-9, true));
+        let relabeled = Series::from_label(&[1.0, 2.0, f64::NAN], &[0, 1, 3]);
+        assert!(!a.approx_eq(&relabeled, 1.0, 1.0, true));
+        assert_series_eq!(a, b, 1e-9, 0.0);
+    }
+
+    #[test]
+    fn check_series_eq_reports_differences() {
+        let a = Series::from(&[1.0, 2.0, f64::NAN]);
+        let c = Series::from(&[5.0, 2.0, 1.0]);
+        let message = check_series_eq(&a, &c, 0.0, 0.0, true).unwrap_err();
+        assert!(
+            message.starts_with("series differ at 2 positions"),
+            "{}",
+            message
+        );
+        assert!(
+            message.contains("[0] left 0 = 1.0, right 0 = 5.0"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn assert_series_eq_panics_on_length_mismatch() {
+        assert_series_eq!(Series::from(&[1.0]), Series::from(&[1.0, 2.0]));
+    }
+}